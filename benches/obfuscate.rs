@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use september_interview_task::task_03::detectors::{EmailDetector, PhoneDetector};
+use september_interview_task::task_03::{obfuscate, Detector};
+
+const EMAILS: [(&str, &str); 2] = [
+    ("email", "local-part@domain-name.com"),
+    (
+        "email_long_local_part",
+        "a.very.long.local.part.with.many.dots.and.letters.in.it@domain-name.com",
+    ),
+];
+
+const PHONES: [(&str, &str); 2] = [
+    ("phone", "+44 123 456 789"),
+    (
+        "phone_many_groups",
+        "+1 (555) 12-34-56-78-90-12-34-56-78-90",
+    ),
+];
+
+/// The whole `obfuscate`, i.e. all the default detectors are tried in order
+fn bench_obfuscate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("obfuscate");
+    for (name, input) in EMAILS.iter().chain(PHONES.iter()) {
+        group.bench_function(*name, |b| {
+            b.iter(|| obfuscate(black_box(input.to_string())))
+        });
+    }
+    group.finish();
+}
+
+/// A single detector, without the ones that go before it
+fn bench_detector<D: Detector>(
+    c: &mut Criterion,
+    group_name: &str,
+    detector: D,
+    inputs: &[(&str, &str)],
+) {
+    let mut group = c.benchmark_group(group_name);
+    for (name, input) in inputs {
+        group.bench_function(*name, |b| {
+            b.iter(|| detector.try_obfuscate(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_email_detector(c: &mut Criterion) {
+    bench_detector(c, "email_detector", EmailDetector, &EMAILS);
+}
+
+fn bench_phone_detector(c: &mut Criterion) {
+    bench_detector(c, "phone_detector", PhoneDetector::default(), &PHONES);
+}
+
+criterion_group!(
+    benches,
+    bench_obfuscate,
+    bench_email_detector,
+    bench_phone_detector
+);
+criterion_main!(benches);
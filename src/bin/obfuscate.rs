@@ -0,0 +1,28 @@
+//! Obfuscates the stdin line by line
+//!
+//! Every line is obfuscated as a whole, the unrecognized lines are printed as is.
+//! With `--text`, the sensitive values are obfuscated within the lines instead.
+
+use september_interview_task::task_03::obfuscate_lines;
+use std::io;
+use std::process;
+
+fn main() {
+    let mut text = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--text" => text = true,
+            _ => {
+                eprintln!("usage: obfuscate [--text] < input");
+                process::exit(2);
+            }
+        }
+    }
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    if let Err(err) = obfuscate_lines(stdin.lock(), stdout.lock(), text) {
+        eprintln!("obfuscate: {}", err);
+        process::exit(1);
+    }
+}
@@ -2,4 +2,4 @@
 
 mod task_01;
 mod task_02;
-mod task_03;
+pub mod task_03;
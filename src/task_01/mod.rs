@@ -1,4 +1,199 @@
 #![allow(clippy::unnecessary_cast)]
+/// `OrdinalPolicy` for the unchecked `Ordinal`, to decide if 0 and negatives are accepted
+mod policy;
+/// `Ordinal` with unchecked construction, any integer is accepted, incl. 0 and negatives
 mod simple;
+mod words;
+/// `Ordinal` with checked construction via `TryFrom`, only integers greater than 0 are accepted
 mod wrapped;
+/// `Ordinal` with checked construction via `TryIntoOrdinal`, only integers greater than 0
+/// are accepted
 mod wrapped2;
+
+use std::fmt;
+use std::fmt::Write;
+
+/// Returns the English ordinal suffix for the number written in `s`, e.g. "st" for "21"
+///
+/// All the `Ordinal` implementations share this function, they differ only in which values
+/// could be wrapped.
+///
+/// I saw the similar implementation on the Internet some time ago, it works on the string
+/// representation, hence it works for any integer type, incl. the negatives
+///
+/// The suffix is meaningful for the decimal representation only, hence `s` must be the plain
+/// `to_string()` of the value. The `Display` impls format the inner value into a local string
+/// first, so the formatter flags, e.g. `{:#}` or `{:+}`, never change the suffix.
+fn ordinal_suffix_for(s: &str) -> &'static str {
+    if s.ends_with('1') && !s.ends_with("11") {
+        "st"
+    } else if s.ends_with('2') && !s.ends_with("12") {
+        "nd"
+    } else if s.ends_with('3') && !s.ends_with("13") {
+        "rd"
+    } else {
+        "th"
+    }
+}
+
+/// Writes the "Nth" string padded to the formatter's width, with its fill and alignment,
+/// e.g. `{:>5}` is "  1st". Strings are left-aligned by default.
+///
+/// Unlike `Formatter::pad`, the precision is ignored, so `{:.2}` never truncates the suffix.
+fn pad_ordinal(f: &mut fmt::Formatter, ordinal: &str) -> fmt::Result {
+    let padding = f
+        .width()
+        .map_or(0, |width| width.saturating_sub(ordinal.chars().count()));
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Left) | None => (0, padding),
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(ordinal)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Ordinals of the days of a month, `ORDINAL_1_TO_31[0]` is "1st"
+pub const ORDINAL_1_TO_31: [&str; 31] = [
+    "1st", "2nd", "3rd", "4th", "5th", "6th", "7th", "8th", "9th", "10th", "11th", "12th", "13th",
+    "14th", "15th", "16th", "17th", "18th", "19th", "20th", "21st", "22nd", "23rd", "24th", "25th",
+    "26th", "27th", "28th", "29th", "30th", "31st",
+];
+
+/// Returns the ordinal for 1 to 31 without allocating, or `None` for any other number
+pub fn ordinal_small(n: u8) -> Option<&'static str> {
+    ORDINAL_1_TO_31.get((n as usize).checked_sub(1)?).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+    use std::convert::TryFrom;
+    use wrapped2::TryIntoOrdinal;
+
+    /// Independent implementation of the English rule, based on the numbers, not the strings
+    fn reference_suffix(n: u32) -> &'static str {
+        match (n % 10, n % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        }
+    }
+
+    #[test]
+    fn implementations_match() {
+        for n in 1..=30 {
+            let expected = simple::Ordinal(n).to_string();
+
+            assert_eq!(expected, wrapped::Ordinal::try_from(n).unwrap().to_string());
+            assert_eq!(expected, n.try_into_ordinal().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn formatter_flags_keep_decimal_suffix() {
+        let big = u64::MAX - 4;
+        assert_eq!("18446744073709551611", big.to_string());
+
+        let test_cases = vec![
+            ("11th", format!("{:#}", simple::Ordinal(11))),
+            ("11th", format!("{:+}", simple::Ordinal(11))),
+            ("-12th", format!("{:+}", simple::Ordinal(-12))),
+            (
+                "13th",
+                format!("{:#}", wrapped::Ordinal::try_from(13).unwrap()),
+            ),
+            ("13th", format!("{:+}", 13.try_into_ordinal().unwrap())),
+            (
+                "18446744073709551611th",
+                format!("{:#}", simple::Ordinal(big)),
+            ),
+            (
+                "18446744073709551611th",
+                format!("{:+}", wrapped::Ordinal::try_from(big).unwrap()),
+            ),
+            (
+                "18446744073709551611th",
+                format!("{:#}", big.try_into_ordinal().unwrap()),
+            ),
+        ];
+
+        for (expected, formatted) in test_cases {
+            assert_eq!(expected, formatted);
+        }
+    }
+
+    #[test]
+    fn padding() {
+        let test_cases = vec![
+            ("  1st", format!("{:>5}", simple::Ordinal(1))),
+            (" 12th", format!("{:>5}", simple::Ordinal(12))),
+            ("123rd", format!("{:>5}", simple::Ordinal(123))),
+            ("1234th", format!("{:>5}", simple::Ordinal(1234))),
+            ("1st  ", format!("{:<5}", simple::Ordinal(1))),
+            ("1st  ", format!("{:5}", simple::Ordinal(1))),
+            (
+                " 2nd ",
+                format!("{:^5}", wrapped::Ordinal::try_from(2).unwrap()),
+            ),
+            ("__3rd", format!("{:_>5}", 3.try_into_ordinal().unwrap())),
+            // the precision doesn't truncate the suffix
+            ("1st", format!("{:.2}", simple::Ordinal(1))),
+            ("  1st", format!("{:>5.2}", simple::Ordinal(1))),
+            (
+                "2nd",
+                format!("{:.1}", wrapped::Ordinal::try_from(2).unwrap()),
+            ),
+            ("3rd", format!("{:.0}", 3.try_into_ordinal().unwrap())),
+        ];
+
+        for (expected, formatted) in test_cases {
+            assert_eq!(expected, formatted);
+        }
+    }
+
+    #[test]
+    fn small() {
+        let test_cases = vec![
+            (Some("1st"), 1),
+            (Some("21st"), 21),
+            (Some("31st"), 31),
+            (None, 0),
+            (None, 32),
+            (None, 255),
+        ];
+
+        for (expected, input) in test_cases {
+            assert_eq!(expected, ordinal_small(input));
+        }
+
+        for n in 1..=31u8 {
+            assert_eq!(simple::Ordinal(n).to_string(), ordinal_small(n).unwrap());
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10_000))]
+
+        #[test]
+        fn suffix_matches_reference(n in 1u32..=1_000_000) {
+            let expected = format!("{}{}", n, reference_suffix(n));
+
+            prop_assert_eq!(&expected, &simple::Ordinal(n).to_string());
+            prop_assert_eq!(&expected, &wrapped::Ordinal::try_from(n).unwrap().to_string());
+            prop_assert_eq!(&expected, &n.try_into_ordinal().unwrap().to_string());
+        }
+    }
+}
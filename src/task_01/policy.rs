@@ -0,0 +1,82 @@
+use crate::task_01::simple::Ordinal;
+use crate::task_01::wrapped2::OrdinalError;
+
+/// Decides which values could be wrapped into the `Ordinal`, the positive ones are always
+/// accepted.
+///
+/// The default policy rejects both 0 and the negatives, the same as the checked `Ordinal`s do,
+/// while the unchecked `Ordinal(x)` accepts everything.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct OrdinalPolicy {
+    /// Accept 0, it is represented as "0th"
+    pub allow_zero: bool,
+    /// Accept the negatives, e.g. -1 is represented as "-1st"
+    pub allow_negative: bool,
+}
+
+impl<T: num::Integer> Ordinal<T> {
+    /// Wraps the value if the policy accepts it, returns an error otherwise
+    ///
+    /// Example usage:
+    ///
+    /// ```rust
+    /// let policy = OrdinalPolicy { allow_zero: true, ..OrdinalPolicy::default() };
+    /// println!("{}", Ordinal::with_policy(0, policy).unwrap()); // prints "0th"
+    /// ```
+    pub fn with_policy(value: T, policy: OrdinalPolicy) -> Result<Self, OrdinalError> {
+        if value.is_zero() && !policy.allow_zero {
+            return Err(OrdinalError::ConvertError);
+        }
+        if value < T::zero() && !policy.allow_negative {
+            return Err(OrdinalError::ConvertError);
+        }
+
+        Ok(Ordinal(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_policy() {
+        let policy = OrdinalPolicy::default();
+
+        assert_eq!("1st", Ordinal::with_policy(1, policy).unwrap().to_string());
+        assert!(Ordinal::with_policy(0, policy).is_err());
+        assert!(Ordinal::with_policy(-1, policy).is_err());
+    }
+
+    #[test]
+    fn allow_zero() {
+        let policy = OrdinalPolicy {
+            allow_zero: true,
+            allow_negative: false,
+        };
+
+        assert_eq!("0th", Ordinal::with_policy(0, policy).unwrap().to_string());
+        assert_eq!(
+            "0th",
+            Ordinal::with_policy(0u8, policy).unwrap().to_string()
+        );
+        assert!(Ordinal::with_policy(-1, policy).is_err());
+    }
+
+    #[test]
+    fn allow_negative() {
+        let policy = OrdinalPolicy {
+            allow_zero: false,
+            allow_negative: true,
+        };
+
+        let test_cases = vec![("-1st", -1), ("-2nd", -2), ("-11th", -11), ("-23rd", -23)];
+        for (expected, input) in test_cases {
+            assert_eq!(
+                expected,
+                Ordinal::with_policy(input, policy).unwrap().to_string()
+            );
+        }
+        assert!(Ordinal::with_policy(0, policy).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::task_01::{ordinal_suffix_for, pad_ordinal};
 use std::fmt;
 use std::fmt::Display;
 
@@ -29,8 +30,27 @@ pub trait IntoOrdinal {
     }
 }
 
-impl IntoOrdinal for i32 {}
+/// The following macro implements `IntoOrdinal` for the given integer type
+macro_rules! impl_into_ordinal {
+    ($typ:ident) => {
+        impl IntoOrdinal for $typ {}
+    };
+}
+
+impl_into_ordinal!(i8);
+impl_into_ordinal!(i16);
+impl_into_ordinal!(i32);
+impl_into_ordinal!(i64);
+impl_into_ordinal!(i128);
 
+impl_into_ordinal!(u8);
+impl_into_ordinal!(u16);
+impl_into_ordinal!(u32);
+impl_into_ordinal!(u64);
+impl_into_ordinal!(u128);
+
+/// The width, the fill and the alignment apply to the whole "Nth" string, e.g. `{:>5}` is
+/// "  1st", the precision is ignored
 impl<T> Display for Ordinal<T>
 where
     T: Display + num::Integer,
@@ -45,18 +65,30 @@ where
         // I add the `num::Integer` constraint to the generic type `T` to make sure the inner
         // value is a number. `num::Integer` is implemented for all primitive integer types
         //
-        // I saw the similar implementation on the Internet some time ago, though it allows
-        // the values of 0 and negatives
-        let suffix = if s.ends_with('1') && !s.ends_with("11") {
-            "st"
-        } else if s.ends_with('2') && !s.ends_with("12") {
-            "nd"
-        } else if s.ends_with('3') && !s.ends_with("13") {
-            "rd"
-        } else {
-            "th"
-        };
-        write!(f, "{}{}", s, suffix)
+        // The suffix is selected the same way for the values of 0 and negatives
+        let suffix = ordinal_suffix_for(&s);
+        pad_ordinal(f, &format!("{}{}", s, suffix))
+    }
+}
+
+impl<T> Ordinal<T>
+where
+    T: Display + num::Integer,
+{
+    /// Returns the ordinal of the absolute value, prefixed with `sign_word` for the negatives,
+    /// e.g. "minus 1st" or "negative 21st". The non-negative values are the same as `to_string()`
+    ///
+    /// Example usage:
+    ///
+    /// ```rust
+    /// println!("{}", Ordinal(-11).to_string_signed("minus")); // prints "minus 11th"
+    /// ```
+    pub fn to_string_signed(&self, sign_word: &str) -> String {
+        let s = self.0.to_string();
+        match s.strip_prefix('-') {
+            Some(abs) => format!("{} {}{}", sign_word, abs, ordinal_suffix_for(abs)),
+            None => self.to_string(),
+        }
     }
 }
 
@@ -71,6 +103,38 @@ pub fn ordinal<T: IntoOrdinal + num::Integer + Display>(input: T) -> String {
     input.into_ordinal().to_string()
 }
 
+/// Returns a phrase like "every 3rd", using the "every other" wording for 2
+///
+/// Example usage:
+///
+/// ```rust
+/// println!("{} day", every_ordinal(2)); // prints "every other day"
+/// println!("{} day", every_ordinal(3)); // prints "every 3rd day"
+/// ```
+pub fn every_ordinal(n: u64) -> String {
+    if n == 2 {
+        "every other".to_string()
+    } else {
+        format!("every {}", Ordinal(n))
+    }
+}
+
+/// Labels every item of the slice with its 1-based ordinal position
+///
+/// Example usage:
+///
+/// ```rust
+/// for (label, item) in label_ordinals(&["gold", "silver"]) {
+///     println!("{}: {}", label, item); // prints "1st: gold", then "2nd: silver"
+/// }
+/// ```
+pub fn label_ordinals<T>(items: &[T]) -> impl Iterator<Item = (String, &T)> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| (Ordinal(i + 1).to_string(), item))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +164,23 @@ mod tests {
         assert_eq!("1st", 1.into_ordinal().to_string())
     }
 
+    #[test]
+    fn trait_types() {
+        assert_eq!("1st", 1i8.into_ordinal().to_string());
+        assert_eq!("2nd", 2i16.into_ordinal().to_string());
+        assert_eq!("3rd", 3i32.into_ordinal().to_string());
+        assert_eq!("-11th", (-11i64).into_ordinal().to_string());
+        assert_eq!(
+            "170141183460469231731687303715884105727th",
+            i128::MAX.into_ordinal().to_string()
+        );
+        assert_eq!("0th", 0u8.into_ordinal().to_string());
+        assert_eq!("12th", 12u16.into_ordinal().to_string());
+        assert_eq!("21st", 21u32.into_ordinal().to_string());
+        assert_eq!("5th", 5u64.into_ordinal().to_string());
+        assert_eq!("102nd", 102u128.into_ordinal().to_string());
+    }
+
     #[test]
     fn ordinals() {
         let test_cases = vec![
@@ -119,4 +200,47 @@ mod tests {
             assert_eq!(expected, ordinal(input));
         }
     }
+
+    #[test]
+    fn labels() {
+        let items = ["a", "b", "c"];
+        let expected = vec![
+            ("1st".to_string(), &"a"),
+            ("2nd".to_string(), &"b"),
+            ("3rd".to_string(), &"c"),
+        ];
+
+        assert_eq!(expected, label_ordinals(&items).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn every() {
+        let test_cases = vec![
+            ("every other", 2),
+            ("every 3rd", 3),
+            ("every 4th", 4),
+            ("every 10th", 10),
+        ];
+
+        for (expected, input) in test_cases {
+            assert_eq!(expected, every_ordinal(input));
+        }
+    }
+
+    #[test]
+    fn signed() {
+        let test_cases = vec![
+            ("minus 1st", -1, "minus"),
+            ("minus 11th", -11, "minus"),
+            ("negative 21st", -21, "negative"),
+            ("21st", 21, "minus"),
+            ("0th", 0, "minus"),
+        ];
+
+        for (expected, input, sign_word) in test_cases {
+            assert_eq!(expected, Ordinal(input).to_string_signed(sign_word));
+        }
+
+        assert_eq!("minus 128th", Ordinal(i8::MIN).to_string_signed("minus"));
+    }
 }
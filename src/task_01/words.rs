@@ -0,0 +1,191 @@
+use crate::task_01::simple::Ordinal;
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(u64, &str); 6] = [
+    (1_000_000_000_000_000_000, "quintillion"),
+    (1_000_000_000_000_000, "quadrillion"),
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// Representation of an ordinal number in a text
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OrdinalForm {
+    /// e.g. "1st"
+    Numeric,
+    /// e.g. "first"
+    Words,
+}
+
+/// Cardinal number below 1000 in words, e.g. "one hundred twenty-three"
+fn hundreds_words(n: u64) -> String {
+    let mut words = Vec::new();
+
+    if n >= 100 {
+        words.push(format!("{} hundred", ONES[(n / 100) as usize]));
+    }
+
+    let rest = n % 100;
+    if rest >= 20 {
+        let tens = TENS[(rest / 10) as usize];
+        if rest % 10 == 0 {
+            words.push(tens.to_string());
+        } else {
+            words.push(format!("{}-{}", tens, ONES[(rest % 10) as usize]));
+        }
+    } else if rest > 0 || n == 0 {
+        words.push(ONES[rest as usize].to_string());
+    }
+
+    words.join(" ")
+}
+
+/// Cardinal number in words, e.g. "one thousand two hundred"
+fn cardinal_words(n: u64) -> String {
+    let mut words = Vec::new();
+    let mut rest = n;
+
+    for &(scale, name) in SCALES.iter() {
+        if rest >= scale {
+            words.push(format!("{} {}", hundreds_words(rest / scale), name));
+            rest %= scale;
+        }
+    }
+
+    if rest > 0 || n == 0 {
+        words.push(hundreds_words(rest));
+    }
+
+    words.join(" ")
+}
+
+/// Returns an ordinal number in words, e.g. "twenty-first"
+///
+/// Only the last word changes, the rest of the number is the same as the cardinal one
+pub fn ordinal_words(n: u64) -> String {
+    let cardinal = cardinal_words(n);
+    let split_at = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+    let (head, last) = cardinal.split_at(split_at);
+
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        tens if tens.ends_with('y') => format!("{}ieth", tens.trim_end_matches('y')),
+        other => format!("{}th", other),
+    };
+
+    format!("{}{}", head, last)
+}
+
+/// Returns an ordinal number with an article, e.g. "the 1st", "an 8th" or "an eighth"
+///
+/// The indefinite article depends on how the number is pronounced, not on how it is written,
+/// hence it is picked by the word form for both forms: "an 11th", but "a 1st".
+///
+/// Example usage:
+///
+/// ```rust
+/// println!("{}", ordinal_with_article(1, true, OrdinalForm::Words)); // prints "the first"
+/// println!("{}", ordinal_with_article(8, false, OrdinalForm::Numeric)); // prints "an 8th"
+/// ```
+pub fn ordinal_with_article(n: u64, definite: bool, form: OrdinalForm) -> String {
+    let words = ordinal_words(n);
+
+    let article = if definite {
+        "the"
+    } else if words.starts_with('e') {
+        // only "eight", "eleven", "eighteen" and "eighty" start with a vowel sound,
+        // "one" starts with a vowel letter, but is pronounced with "w"
+        "an"
+    } else {
+        "a"
+    };
+
+    match form {
+        OrdinalForm::Numeric => format!("{} {}", article, Ordinal(n)),
+        OrdinalForm::Words => format!("{} {}", article, words),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words() {
+        let test_cases = vec![
+            ("zeroth", 0),
+            ("first", 1),
+            ("second", 2),
+            ("third", 3),
+            ("eighth", 8),
+            ("eleventh", 11),
+            ("twelfth", 12),
+            ("twentieth", 20),
+            ("twenty-first", 21),
+            ("one hundredth", 100),
+            ("one hundred first", 101),
+            ("one thousand two hundred thirty-fourth", 1234),
+            ("one millionth", 1_000_000),
+        ];
+
+        for (expected, input) in test_cases {
+            assert_eq!(expected, ordinal_words(input));
+        }
+    }
+
+    #[test]
+    fn articles() {
+        let test_cases = vec![
+            ("the 1st", 1, true, OrdinalForm::Numeric),
+            ("the first", 1, true, OrdinalForm::Words),
+            ("a 1st", 1, false, OrdinalForm::Numeric),
+            ("a 2nd", 2, false, OrdinalForm::Numeric),
+            ("an 8th", 8, false, OrdinalForm::Numeric),
+            ("an eighth", 8, false, OrdinalForm::Words),
+            ("an 11th", 11, false, OrdinalForm::Numeric),
+            ("an eleventh", 11, false, OrdinalForm::Words),
+            ("an 18th", 18, false, OrdinalForm::Numeric),
+            ("an 80th", 80, false, OrdinalForm::Numeric),
+            ("a 100th", 100, false, OrdinalForm::Numeric),
+            ("a one hundredth", 100, false, OrdinalForm::Words),
+        ];
+
+        for (expected, n, definite, form) in test_cases {
+            assert_eq!(expected, ordinal_with_article(n, definite, form));
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use crate::task_01::wrapped2::OrdinalError;
+use crate::task_01::{ordinal_suffix_for, pad_ordinal};
 use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::Display;
+use std::num::NonZeroU32;
+use std::ops::{Add, Sub};
 
 /// Ordinal(T) wraps a value to be represented as an ordinal number.
 ///
@@ -18,12 +22,13 @@ use std::fmt::Display;
 /// This one is better in my opinion because it allows you to be sure that inner value
 /// is in a half-closed intercval from 1 to infiniti, i.e. [1..). Also it's enforced that
 /// the inner value is integer.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Ordinal<T>(T);
 
-/// The following macro implements `TryFrom<T>` for `Ordinal<U>` where `T` is the same type as `U`
+/// The following macro implements `TryFrom<T>` for `Ordinal<U>` where `T` is the same type as `U`,
+/// and `from_clamped()` for the same types
 ///
-/// Providing this method as the only way to create an Ordinal type allows us to make sure
+/// Providing these methods as the only ways to create an Ordinal type allows us to make sure
 /// the inner value is Integer and is greater than zero
 macro_rules! impl_try_from_ordinal {
     ($typ:ident) => {
@@ -38,6 +43,20 @@ macro_rules! impl_try_from_ordinal {
                 }
             }
         }
+
+        impl Ordinal<$typ> {
+            /// Wraps the value, clamping it to 1 if it's not greater than zero.
+            ///
+            /// Unlike `try_from()`, this never fails, but it intentionally loses information:
+            /// all non-positive values become `Ordinal(1)`, i.e. "1st".
+            pub fn from_clamped(value: $typ) -> Self {
+                if value <= 0 {
+                    Ordinal(1)
+                } else {
+                    Ordinal(value)
+                }
+            }
+        }
     };
 }
 
@@ -53,6 +72,41 @@ impl_try_from_ordinal!(u64);
 
 // more implementations (e.g. for u128 and i128) could be added with conditional compilation
 
+/// `NonZeroU32` is always greater than zero, so the conversion never fails
+impl From<NonZeroU32> for Ordinal<u32> {
+    fn from(value: NonZeroU32) -> Self {
+        Ordinal(value.get())
+    }
+}
+
+impl Ordinal<u32> {
+    /// Returns the inner value as `NonZeroU32`, the invariant guarantees it's not zero
+    pub fn as_non_zero(&self) -> NonZeroU32 {
+        NonZeroU32::new(self.0).expect("Ordinal inner value is greater than zero")
+    }
+}
+
+/// Arithmetic keeps the invariant, i.e. the result is an error if it's not greater than zero
+/// or if it overflows
+impl Add<u64> for Ordinal<u64> {
+    type Output = Result<Ordinal<u64>, OrdinalError>;
+
+    fn add(self, rhs: u64) -> Self::Output {
+        let value = self.0.checked_add(rhs).ok_or(OrdinalError::ConvertError)?;
+        Ordinal::try_from(value).map_err(|_| OrdinalError::ConvertError)
+    }
+}
+
+impl Sub<u64> for Ordinal<u64> {
+    type Output = Result<Ordinal<u64>, OrdinalError>;
+
+    fn sub(self, rhs: u64) -> Self::Output {
+        let value = self.0.checked_sub(rhs).ok_or(OrdinalError::ConvertError)?;
+        Ordinal::try_from(value).map_err(|_| OrdinalError::ConvertError)
+    }
+}
+
+/// Pads the same way as `simple::Ordinal`: the precision is ignored
 impl<T> Display for Ordinal<T>
 where
     T: Display + num::Integer,
@@ -64,16 +118,8 @@ where
         //
         // Fortunately, with this implementation it's impossible to initialize a struct with
         // a negative number
-        let suffix = if s.ends_with('1') && !s.ends_with("11") {
-            "st"
-        } else if s.ends_with('2') && !s.ends_with("12") {
-            "nd"
-        } else if s.ends_with('3') && !s.ends_with("13") {
-            "rd"
-        } else {
-            "th"
-        };
-        write!(f, "{}{}", s, suffix)
+        let suffix = ordinal_suffix_for(&s);
+        pad_ordinal(f, &format!("{}{}", s, suffix))
     }
 }
 
@@ -81,6 +127,7 @@ where
 mod tests {
     use super::*;
 
+    use std::collections::HashSet;
     use std::convert::TryInto;
 
     #[test]
@@ -95,6 +142,25 @@ mod tests {
         assert_eq!(Ok(Ordinal(1)), Ordinal::try_from(1 as u64));
     }
 
+    #[test]
+    fn non_zero() {
+        let test_cases = vec![
+            ("1st", 1),
+            ("2nd", 2),
+            ("11th", 11),
+            ("4294967295th", u32::MAX),
+        ];
+
+        for (expected, input) in test_cases {
+            let non_zero = NonZeroU32::new(input).unwrap();
+            let ordinal = Ordinal::from(non_zero);
+
+            assert_eq!(expected, ordinal.to_string());
+            assert_eq!(Ok(ordinal), Ordinal::try_from(input));
+            assert_eq!(non_zero, ordinal.as_non_zero());
+        }
+    }
+
     #[test]
     fn various() {
         assert_eq!(Ok(Ordinal(1)), Ordinal::try_from(1));
@@ -145,4 +211,46 @@ mod tests {
             assert_eq!(expected, Ordinal::try_from(input).unwrap().to_string())
         }
     }
+
+    #[test]
+    fn clamped() {
+        let test_cases = vec![
+            (Ordinal(1), -5),
+            (Ordinal(1), 0),
+            (Ordinal(1), 1),
+            (Ordinal(7), 7),
+        ];
+
+        for (expected, input) in test_cases {
+            assert_eq!(expected, Ordinal::<i32>::from_clamped(input));
+        }
+
+        assert_eq!("1st", Ordinal::<u8>::from_clamped(0).to_string());
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(Ordinal(5), (Ordinal(3_u64) + 2).unwrap());
+        assert_eq!(Ordinal(1), (Ordinal(3_u64) - 2).unwrap());
+        assert_eq!("5th", (Ordinal(3_u64) + 2).unwrap().to_string());
+
+        assert!((Ordinal(1_u64) - 5).is_err());
+        assert!((Ordinal(3_u64) - 3).is_err());
+        assert!((Ordinal(u64::MAX) + 1).is_err());
+    }
+
+    #[test]
+    fn hash_set() {
+        let mut set = HashSet::new();
+        set.insert(Ordinal::try_from(1).unwrap());
+        set.insert(Ordinal::try_from(2).unwrap());
+        set.insert(Ordinal::try_from(3).unwrap());
+        set.insert(Ordinal::try_from(1).unwrap());
+
+        assert_eq!(3, set.len());
+        assert!(set.contains(&Ordinal(1)));
+        assert!(set.contains(&Ordinal(2)));
+        assert!(!set.contains(&Ordinal(4)));
+        assert_ne!(Ordinal(1), Ordinal(2));
+    }
 }
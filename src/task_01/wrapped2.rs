@@ -1,3 +1,4 @@
+use crate::task_01::{ordinal_suffix_for, pad_ordinal};
 use std::fmt;
 use std::fmt::Display;
 
@@ -17,7 +18,7 @@ use std::fmt::Display;
 /// This one is better in my opinion because it allows you to be sure that inner value
 /// is in a half-closed intercval from 1 to infiniti, i.e. [1..). Also it's enforced that
 /// the inner value is integer.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Ordinal<T>(T);
 
 /// This trait is created to replace the `TryInto` trait of the std library.
@@ -59,6 +60,7 @@ impl_try_into_ordinal!(u64);
 
 // more implementations (e.g. for u128 and i128) could be added with conditional compilation
 
+/// Pads the same way as `simple::Ordinal`: the precision is ignored
 impl<T> Display for Ordinal<T>
 where
     T: Display + num::Integer,
@@ -70,16 +72,33 @@ where
         //
         // Fortunately, with this implementation it's impossible to initialize a struct with
         // a negative number
-        let suffix = if s.ends_with('1') && !s.ends_with("11") {
-            "st"
-        } else if s.ends_with('2') && !s.ends_with("12") {
-            "nd"
-        } else if s.ends_with('3') && !s.ends_with("13") {
-            "rd"
-        } else {
-            "th"
-        };
-        write!(f, "{}{}", s, suffix)
+        let suffix = ordinal_suffix_for(&s);
+        pad_ordinal(f, &format!("{}{}", s, suffix))
+    }
+}
+
+impl<T> Ordinal<T>
+where
+    T: Display + num::Integer,
+{
+    /// Returns the ordinal with `sep` inserted between every three digits from the right,
+    /// e.g. "1,000,001st" for `,`
+    ///
+    /// The suffix is selected by the ungrouped number, the inner value is always positive,
+    /// so there is no sign to take care of
+    pub fn to_string_grouped(&self, sep: char) -> String {
+        let s = self.0.to_string();
+        let mut grouped = String::with_capacity(s.len() + s.len() / 3 + 2);
+
+        for (i, ch) in s.chars().enumerate() {
+            if i > 0 && (s.len() - i) % 3 == 0 {
+                grouped.push(sep);
+            }
+            grouped.push(ch);
+        }
+
+        grouped.push_str(ordinal_suffix_for(&s));
+        grouped
     }
 }
 
@@ -111,10 +130,50 @@ where
     }
 }
 
+/// An iterator that converts every integer of the inner iterator into an ordinal String,
+/// see `OrdinalIterExt::ordinals`
+pub struct OrdinalIter<I>(I);
+
+impl<I> Iterator for OrdinalIter<I>
+where
+    I: Iterator,
+    I::Item: TryIntoOrdinal<I::Item, Error = &'static str> + Display + num::Integer,
+{
+    type Item = Result<String, OrdinalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(ordinal)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Extension trait that adds `.ordinals()` to any iterator of integers
+///
+/// Example usage:
+///
+/// ```rust
+/// let ordinals = (1..=3).ordinals().collect::<Result<Vec<_>, _>>().unwrap();
+/// println!("{:?}", ordinals); // prints `["1st", "2nd", "3rd"]`
+/// ```
+pub trait OrdinalIterExt: Iterator + Sized {
+    /// Yields the ordinal representation of every item, or an error for the items
+    /// that are not greater than zero
+    fn ordinals(self) -> OrdinalIter<Self> {
+        OrdinalIter(self)
+    }
+}
+
+impl<I: Iterator> OrdinalIterExt for I {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::collections::HashSet;
+
     #[test]
     fn types() {
         assert_eq!(Ok(Ordinal(1)), (1 as i8).try_into_ordinal());
@@ -202,4 +261,58 @@ mod tests {
             assert!(ordinal(input).is_err());
         }
     }
+
+    #[test]
+    fn hash_set() {
+        let mut set = HashSet::new();
+        set.insert(1.try_into_ordinal().unwrap());
+        set.insert(2.try_into_ordinal().unwrap());
+        set.insert(3.try_into_ordinal().unwrap());
+        set.insert(1.try_into_ordinal().unwrap());
+
+        assert_eq!(3, set.len());
+        assert!(set.contains(&Ordinal(1)));
+        assert!(set.contains(&Ordinal(2)));
+        assert!(!set.contains(&Ordinal(4)));
+        assert_ne!(Ordinal(1), Ordinal(2));
+    }
+
+    #[test]
+    fn ordinals_iter() {
+        let ordinals = (1..=5).ordinals().collect::<Vec<_>>();
+        assert_eq!(5, ordinals.len());
+        assert_eq!(
+            vec!["1st", "2nd", "3rd", "4th", "5th"],
+            ordinals.into_iter().collect::<Result<Vec<_>, _>>().unwrap()
+        );
+
+        let numbers = [1u8, 0, 2];
+        let ordinals = numbers.iter().copied().ordinals().collect::<Vec<_>>();
+        assert_eq!(3, ordinals.len());
+        assert_eq!("1st", ordinals[0].as_ref().unwrap());
+        assert!(ordinals[1].is_err());
+        assert_eq!("2nd", ordinals[2].as_ref().unwrap());
+    }
+
+    #[test]
+    fn grouped() {
+        let test_cases = vec![
+            ("1st", 1u64, ','),
+            ("999th", 999, ','),
+            ("1,000th", 1000, ','),
+            ("1.000th", 1000, '.'),
+            ("12,345th", 12345, ','),
+            ("1,000,001st", 1_000_001, ','),
+            ("1.000.001st", 1_000_001, '.'),
+            ("1,000,011th", 1_000_011, ','),
+            ("100,000,002nd", 100_000_002, ','),
+        ];
+
+        for (expected, input, sep) in test_cases {
+            assert_eq!(
+                expected,
+                input.try_into_ordinal().unwrap().to_string_grouped(sep)
+            );
+        }
+    }
 }
@@ -1,10 +1,16 @@
+use crate::task_01::ordinal_small;
 use chrono::format::ParseError;
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Weekday};
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 
 /// To be honest, number of Sundays could be calculated just using the week-of-the-year number,
 /// but I decided to generalize it a bit, just to be sure that it is easy to modify the day
 /// the week.
-
+#[derive(Copy, Clone, Debug)]
 pub struct WeekdaysCounter {
     start_date: NaiveDate,
     end_date: NaiveDate,
@@ -18,51 +24,512 @@ impl WeekdaysCounter {
         }
     }
 
-    /// A bit weird way to count the dates, but it does the job.
+    /// Creates a counter for the inclusive range, i.e. both `start()` and `end()` are counted
     ///
-    /// The idea is to count a number of 'full weeks' that fit into the timeframe starting with
-    /// the target weekday.
-    fn count(&self, day_of_week: Weekday) -> u32 {
-        let (year_day_from, year_day_to) = (self.start_date.ordinal(), self.end_date.ordinal());
-        if year_day_to < year_day_from {
-            return 0;
+    /// An empty range, e.g. `b..=a` for `a < b`, is inverted, the same as for the other
+    /// constructors. Use `count_strict` to count nothing for it, as `RangeInclusive` does.
+    pub fn from_range(range: RangeInclusive<NaiveDate>) -> Self {
+        let (start_date, end_date) = range.into_inner();
+        Self::new(start_date, end_date)
+    }
+
+    /// Returns the first date of the range, as it was passed
+    pub fn start(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    /// Returns the last date of the range, as it was passed
+    pub fn end(&self) -> NaiveDate {
+        self.end_date
+    }
+
+    /// Returns `true` if `start_date` goes after `end_date`
+    pub fn is_inverted(&self) -> bool {
+        self.start_date > self.end_date
+    }
+
+    /// Returns the same range with `start_date` and `end_date` swapped if it is inverted
+    pub fn normalized(self) -> Self {
+        if self.is_inverted() {
+            Self::new(self.end_date, self.start_date)
+        } else {
+            self
         }
+    }
 
-        // total number of days in a timeframe
-        let num_days = year_day_to - year_day_from;
+    /// Returns a total number of days in a timeframe, or `None` if the range is inverted.
+    ///
+    /// The day of the year can't be used here, since the dates may belong to different years
+    /// The widest chrono range is about 190 million days, so it always fits into `u32`,
+    /// but it is saturated anyway to never panic on the cast.
+    fn num_days(&self) -> Option<u32> {
+        let num_days = (self.end_date - self.start_date).num_days();
+        if num_days < 0 {
+            None
+        } else {
+            Some(u32::try_from(num_days).unwrap_or(u32::MAX))
+        }
+    }
 
-        // trying to calculate the offset between the `start_date` and the next weekday.
-        let sign_start_diff: i32 = day_of_week.num_days_from_monday() as i32
-            - self.start_date.weekday().num_days_from_monday() as i32;
+    /// Returns a number of days from `start_date` to the first `day_of_week` in the range,
+    /// or `None` if there is no such weekday in the range
+    pub fn first_match_offset(&self, day_of_week: Weekday) -> Option<u32> {
+        self.first_match_offset_with(day_of_week, WeekStart::default())
+    }
 
+    /// The same as `first_match_offset`, with the weekdays numbered from `week_start`
+    fn first_match_offset_with(&self, day_of_week: Weekday, week_start: WeekStart) -> Option<u32> {
+        let num_days = self.num_days()?;
+
+        // trying to calculate the offset between the `start_date` and the next weekday.
+        //
         // if this fits this week, the diff is a positive number up to 6
         // (counting weekdays from 0 to 6, or from 1 to 7). Otherwise, it is negative,
-        // hence adding it up to 7 will give us the offset.
-        let start_offset = if sign_start_diff >= 0 {
-            sign_start_diff
-        } else {
-            7 + sign_start_diff
-        };
+        // hence adding it up to 7 will give us the offset. Adding 7 first keeps it unsigned.
+        let start_offset = (7 + week_start.days_from_start(day_of_week)
+            - week_start.days_from_start(self.start_date.weekday()))
+            % 7;
 
         // sometimes the offset is out of the date range
-        if (num_days as i32) < start_offset {
+        if num_days < start_offset {
+            return None;
+        }
+
+        Some(start_offset)
+    }
+
+    /// Returns a number of distinct ISO-8601 weeks, i.e. `(iso_year, iso_week)` pairs,
+    /// covered by the range. Returns 0 for inverted ranges.
+    ///
+    /// ISO weeks always start on Monday, so instead of collecting `iso_week()` of every day,
+    /// it is enough to count the Mondays between the weeks of `start_date` and `end_date`.
+    /// This also covers the week 52/53 to week 1 wrap, since no week numbers are compared.
+    pub fn iso_week_span(&self) -> u32 {
+        if self.is_inverted() {
             return 0;
         }
 
-        // `+1` is needed since we are counting with the last day included
-        (num_days - start_offset as u32) / 7 + 1
+        // the days between the Mondays of both weeks, computed without shifting the dates,
+        // since the Monday of the earliest date is out of the chrono range
+        let num_days = (self.end_date - self.start_date).num_days()
+            + self.start_date.weekday().num_days_from_monday() as i64
+            - self.end_date.weekday().num_days_from_monday() as i64;
+
+        u32::try_from(num_days / 7 + 1).unwrap_or(u32::MAX)
+    }
+
+    /// Returns a number of complete 7-day weeks in the range, regardless of the weekday they
+    /// start on. An inverted range is normalized first, the same as in `count`.
+    ///
+    /// The last day is included, so the span is `num_days + 1`. Any weekday is counted either
+    /// `whole_weeks` or `whole_weeks + 1` times.
+    pub fn whole_weeks(&self) -> u32 {
+        self.normalized()
+            .num_days()
+            .map_or(0, |num_days| num_days.saturating_add(1) / 7)
+    }
+
+    /// Counts the weekdays in the range, an inverted range is normalized first.
+    ///
+    /// Use `count_strict` to get 0 for inverted ranges instead.
+    fn count(&self, day_of_week: Weekday) -> u32 {
+        self.count_with_week_start(day_of_week, WeekStart::default())
+    }
+
+    /// The same as `count`, with the weekdays numbered from `week_start`
+    ///
+    /// The start of the week only affects the internal offset math for now, the number
+    /// of the given weekday is the same for any start.
+    pub fn count_with_week_start(&self, day_of_week: Weekday, week_start: WeekStart) -> u32 {
+        self.normalized().count_strict_with(day_of_week, week_start)
+    }
+
+    /// Checks whether the range has at least one `day_of_week`, an inverted range is normalized
+    /// first, the same as in `count`
+    ///
+    /// The same as `count(day_of_week) > 0`, but only the first match is looked for
+    pub fn contains(&self, day_of_week: Weekday) -> bool {
+        let counter = self.normalized();
+        match (counter.num_days(), counter.first_match_offset(day_of_week)) {
+            (Some(num_days), Some(offset)) => offset <= num_days,
+            _ => false,
+        }
+    }
+
+    /// Counts the dates in the range that satisfy the predicate, an inverted range is normalized
+    /// first, the same as in `count`.
+    ///
+    /// Unlike `count`, every date in the range is checked, so prefer `count` for the weekdays
+    pub fn count_where<F: Fn(NaiveDate) -> bool>(&self, pred: F) -> u32 {
+        let counter = self.normalized();
+        let num_days = match counter.num_days() {
+            Some(num_days) => num_days,
+            None => return 0,
+        };
+
+        (0..=num_days as i64)
+            .map(|offset| counter.start_date + chrono::Duration::days(offset))
+            .filter(|&date| pred(date))
+            .count() as u32
+    }
+
+    /// Counts the dates in the range that are working days of the calendar, an inverted range
+    /// is normalized first, the same as in `count`
+    pub fn count_working_days(&self, cal: &dyn Calendar) -> u32 {
+        self.count_where(|date| cal.is_working_day(date))
+    }
+
+    /// Counts the `day_of_week` weekdays and finds the first and the last of them,
+    /// an inverted range is normalized first, the same as in `count`
+    pub fn summary(&self, day_of_week: Weekday) -> WeekdaySummary {
+        let counter = self.normalized();
+        let count = counter.count_strict(day_of_week);
+        let first = counter
+            .first_match_offset(day_of_week)
+            .map(|offset| counter.start_date + chrono::Duration::days(offset as i64));
+        let last =
+            first.map(|first| first + chrono::Duration::weeks(count.saturating_sub(1) as i64));
+
+        WeekdaySummary { count, first, last }
+    }
+
+    /// Counts the days that match any of the given weekdays.
+    ///
+    /// A weekday that is listed more than once is counted once.
+    pub fn count_any_of(&self, days: &[Weekday]) -> u32 {
+        days.iter()
+            .enumerate()
+            .filter(|(i, day)| !days[..*i].contains(day))
+            .map(|(_, &day)| self.count(day))
+            .sum()
+    }
+
+    /// A bit weird way to count the dates, but it does the job.
+    ///
+    /// The idea is to count a number of 'full weeks' that fit into the timeframe starting with
+    /// the target weekday. Returns 0 for inverted ranges.
+    pub fn count_strict(&self, day_of_week: Weekday) -> u32 {
+        self.count_strict_with(day_of_week, WeekStart::default())
+    }
+
+    fn count_strict_with(&self, day_of_week: Weekday, week_start: WeekStart) -> u32 {
+        match (
+            self.num_days(),
+            self.first_match_offset_with(day_of_week, week_start),
+        ) {
+            // `+1` is needed since we are counting with the last day included,
+            // it can't overflow after the division
+            (Some(num_days), Some(start_offset)) => num_days
+                .checked_sub(start_offset)
+                .map_or(0, |days| days / 7 + 1),
+            _ => 0,
+        }
+    }
+}
+
+/// The first day of the week, e.g. Sunday in the US, see `WeekdaysCounter::count_with_week_start`
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum WeekStart {
+    /// The ISO-8601 week
+    #[default]
+    Mon,
+    Sun,
+}
+
+impl WeekStart {
+    /// Returns a number of days from the start of the week to the `day`, from 0 to 6
+    fn days_from_start(self, day: Weekday) -> u32 {
+        match self {
+            WeekStart::Mon => day.num_days_from_monday(),
+            WeekStart::Sun => day.num_days_from_sunday(),
+        }
+    }
+}
+
+/// A number of weekdays in a range, together with the first and the last of them,
+/// see `WeekdaysCounter::summary`. The dates are `None` if the count is 0.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WeekdaySummary {
+    pub count: u32,
+    pub first: Option<NaiveDate>,
+    pub last: Option<NaiveDate>,
+}
+
+/// Decides which dates are working days, see `WeekdaysCounter::count_working_days`
+///
+/// Implement it to describe the working days of a region, e.g. a different weekend
+/// or the local holidays.
+pub trait Calendar {
+    fn is_working_day(&self, date: NaiveDate) -> bool;
+}
+
+/// A calendar where the working days are Monday to Friday, except for the given holidays
+#[derive(Clone, Debug, Default)]
+pub struct WesternBankingCalendar {
+    holidays: HashSet<NaiveDate>,
+}
+
+impl WesternBankingCalendar {
+    pub fn new<I: IntoIterator<Item = NaiveDate>>(holidays: I) -> Self {
+        Self {
+            holidays: holidays.into_iter().collect(),
+        }
     }
 }
 
+impl Calendar for WesternBankingCalendar {
+    fn is_working_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.holidays.contains(&date)
+    }
+}
+
+impl From<RangeInclusive<NaiveDate>> for WeekdaysCounter {
+    fn from(range: RangeInclusive<NaiveDate>) -> Self {
+        Self::from_range(range)
+    }
+}
+
+/// A range of dates written as `dd-mm-yyyy..dd-mm-yyyy`, e.g. `01-05-2021..30-05-2021`
+///
+/// The range is inclusive on both sides
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// An error returned when a `DateRange` couldn't be parsed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DateRangeParseError {
+    /// There is no `..` between the dates
+    MissingSeparator,
+    /// One of the dates is not in the `dd-mm-yyyy` format
+    InvalidDate(ParseError),
+}
+
+impl fmt::Display for DateRangeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateRangeParseError::MissingSeparator => write!(f, "date range has no `..`"),
+            DateRangeParseError::InvalidDate(err) => {
+                write!(f, "date range has an invalid date: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateRangeParseError {}
+
+impl From<ParseError> for DateRangeParseError {
+    fn from(err: ParseError) -> Self {
+        DateRangeParseError::InvalidDate(err)
+    }
+}
+
+impl FromStr for DateRange {
+    type Err = DateRangeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (date_from, date_to) = s
+            .split_once("..")
+            .ok_or(DateRangeParseError::MissingSeparator)?;
+
+        let format = "%d-%m-%Y";
+        Ok(DateRange {
+            start: NaiveDate::parse_from_str(date_from, format)?,
+            end: NaiveDate::parse_from_str(date_to, format)?,
+        })
+    }
+}
+
+impl DateRange {
+    /// Returns a number of `day_of_week` weekdays in the range, or 0 if the range is inverted
+    pub fn count(&self, day_of_week: Weekday) -> u32 {
+        WeekdaysCounter::new(self.start, self.end).count_strict(day_of_week)
+    }
+}
+
+/// Fluent configuration of a `WeekdaysCounter` that counts several weekdays at once
+///
+/// ```rust
+/// let count = WeekdaysCounterBuilder::default()
+///     .from(start_date)
+///     .to(end_date)
+///     .include(Weekday::Mon)
+///     .include(Weekday::Fri)
+///     .count();
+/// ```
+#[derive(Default)]
+pub struct WeekdaysCounterBuilder {
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    days: Vec<Weekday>,
+}
+
+impl WeekdaysCounterBuilder {
+    pub fn from(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn to(mut self, end_date: NaiveDate) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    /// Including the same weekday more than once has no effect
+    pub fn include(mut self, day_of_week: Weekday) -> Self {
+        if !self.days.contains(&day_of_week) {
+            self.days.push(day_of_week);
+        }
+        self
+    }
+
+    /// Returns a total number of the included weekdays in the range.
+    ///
+    /// If either end of the range is not set, there is nothing to count, hence it returns 0
+    pub fn count(&self) -> u32 {
+        match (self.start_date, self.end_date) {
+            (Some(start_date), Some(end_date)) => {
+                let counter = WeekdaysCounter::new(start_date, end_date);
+                self.days.iter().map(|&day| counter.count_strict(day)).sum()
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Formats the date with the ordinal day of month, e.g. "May 1st, 2021"
+pub fn format_date_with_ordinal(date: NaiveDate) -> String {
+    format_date_ordinal_locale(date, DateStyle::American)
+}
+
+/// The order of the day and the month in `format_date_ordinal_locale`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DateStyle {
+    /// "1st of May 2021"
+    British,
+    /// "May 1st, 2021"
+    American,
+}
+
+/// Formats the date with the ordinal day of month in the given style,
+/// e.g. "1st of May 2021" or "May 1st, 2021"
+pub fn format_date_ordinal_locale(date: NaiveDate, style: DateStyle) -> String {
+    // a day of month is always in 1..=31, so it's in the table
+    let day = ordinal_small(date.day() as u8).unwrap_or_default();
+    let month = date.format("%B");
+
+    match style {
+        DateStyle::British => format!("{} of {} {}", day, month, date.year()),
+        DateStyle::American => format!("{} {}, {}", month, day, date.year()),
+    }
+}
+
+/// Parses a weekday from its English name, either full or the three-letter abbreviation,
+/// e.g. `Sunday` or `sun`. The case is ignored.
+///
+/// Returns `None` if the name is not recognized
+pub fn parse_weekday(s: &str) -> Option<Weekday> {
+    s.parse::<Weekday>().ok()
+}
+
 /// Returns a number of Sundays in the provided date range
 ///
+/// The range is inclusive on both sides. The dates could be either `&str` or `String`
+pub fn count_sundays<S: AsRef<str>>((date_from, date_to): (S, S)) -> Result<u32, ParseError> {
+    let format = "%d-%m-%Y";
+    let start_date = NaiveDate::parse_from_str(date_from.as_ref(), format)?;
+    let end_date = NaiveDate::parse_from_str(date_to.as_ref(), format)?;
+
+    Ok(WeekdaysCounter::new(start_date, end_date).count_strict(Weekday::Sun))
+}
+
+/// Returns a number of weekend days (Saturdays and Sundays) in the provided date range
+///
 /// The range is inclusive on both sides
-pub fn count_sundays((date_from, date_to): (&str, &str)) -> Result<u32, ParseError> {
+pub fn count_weekend_days((date_from, date_to): (&str, &str)) -> Result<u32, ParseError> {
     let format = "%d-%m-%Y";
     let start_date = NaiveDate::parse_from_str(date_from, format)?;
     let end_date = NaiveDate::parse_from_str(date_to, format)?;
 
-    Ok(WeekdaysCounter::new(start_date, end_date).count(Weekday::Sun))
+    let counter = WeekdaysCounter::new(start_date, end_date);
+    Ok(counter.count_strict(Weekday::Sat) + counter.count_strict(Weekday::Sun))
+}
+
+/// Returns a total number of `day_of_week` weekdays in all of the provided date ranges
+///
+/// Every range is counted independently and is inclusive on both sides, hence the days
+/// of overlapping ranges are counted once per range.
+/// Returns the first error if any of the dates could not be parsed.
+pub fn count_weekday_multi(
+    ranges: &[(&str, &str)],
+    day_of_week: Weekday,
+) -> Result<u32, ParseError> {
+    let format = "%d-%m-%Y";
+    let mut total = 0;
+
+    for (date_from, date_to) in ranges {
+        let start_date = NaiveDate::parse_from_str(date_from, format)?;
+        let end_date = NaiveDate::parse_from_str(date_to, format)?;
+        total += WeekdaysCounter::new(start_date, end_date).count_strict(day_of_week);
+    }
+
+    Ok(total)
+}
+
+/// Returns a number of `day` weekdays in the window of `window` from `start`, i.e. between
+/// `start` and `start + window`, inclusive on both sides
+///
+/// Only the whole days of the window are taken into account. A negative window goes back
+/// from `start`, the range is normalized then. The end is saturated to the chrono date range.
+pub fn count_weekday_window(start: NaiveDate, window: chrono::Duration, day: Weekday) -> u32 {
+    let end = start
+        .checked_add_signed(window)
+        .unwrap_or(if window < chrono::Duration::zero() {
+            NaiveDate::MIN
+        } else {
+            NaiveDate::MAX
+        });
+
+    WeekdaysCounter::new(start, end).count(day)
+}
+
+/// Returns a number of `day_of_week` weekdays between two timestamps
+///
+/// The count is based on the calendar dates in the timestamps' time zone, i.e. `from` and `to`
+/// are converted to the local dates first, the time of the day is ignored. Use `with_timezone`
+/// to count in another zone, the same instant could fall on different dates near midnight.
+/// The range is inclusive on both sides.
+pub fn count_weekday_tz<Tz: TimeZone>(from: DateTime<Tz>, to: DateTime<Tz>, day: Weekday) -> u32 {
+    let start_date = from.naive_local().date();
+    let end_date = to.naive_local().date();
+
+    WeekdaysCounter::new(start_date, end_date).count_strict(day)
+}
+
+/// Returns a number of `day` weekdays between the `time` crate dates
+///
+/// The dates are converted to the chrono ones, so the logic is the same as in the other
+/// counters. The range is inclusive on both sides, an inverted range counts 0.
+/// Available with the `time` feature.
+#[cfg(feature = "time")]
+pub fn count_weekday_time(from: time::Date, to: time::Date, day: time::Weekday) -> u32 {
+    let to_chrono = |date: time::Date| {
+        NaiveDate::from_yo_opt(date.year(), date.ordinal() as u32)
+            .expect("`time` dates are valid chrono dates")
+    };
+    let day = match day {
+        time::Weekday::Monday => Weekday::Mon,
+        time::Weekday::Tuesday => Weekday::Tue,
+        time::Weekday::Wednesday => Weekday::Wed,
+        time::Weekday::Thursday => Weekday::Thu,
+        time::Weekday::Friday => Weekday::Fri,
+        time::Weekday::Saturday => Weekday::Sat,
+        time::Weekday::Sunday => Weekday::Sun,
+    };
+
+    WeekdaysCounter::new(to_chrono(from), to_chrono(to)).count_strict(day)
 }
 
 #[cfg(test)]
@@ -165,7 +632,7 @@ mod tests {
         for (expected, weekday) in test_cases {
             assert_eq!(
                 expected,
-                WeekdaysCounter::new(start_date, end_date).count(weekday)
+                WeekdaysCounter::new(start_date, end_date).count_strict(weekday)
             );
         }
     }
@@ -193,4 +660,768 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn weekend_days() {
+        assert_eq!(
+            10,
+            count_weekend_days(("01-05-2021", "31-05-2021")).unwrap()
+        );
+
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("01-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("31-05-2021", format).unwrap();
+        let counter = WeekdaysCounter::new(start_date, end_date);
+        let business_days: u32 = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ]
+        .iter()
+        .map(|&day| counter.count(day))
+        .sum();
+        let num_days = (end_date - start_date).num_days() as u32;
+
+        assert_eq!(
+            num_days + 1 - business_days,
+            count_weekend_days(("01-05-2021", "31-05-2021")).unwrap()
+        );
+    }
+
+    #[test]
+    fn weekend_days_inverted() {
+        assert_eq!(0, count_weekend_days(("02-05-2021", "01-05-2021")).unwrap());
+    }
+
+    #[test]
+    fn builder() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("03-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("09-05-2021", format).unwrap();
+
+        let builder = WeekdaysCounterBuilder::default()
+            .from(start_date)
+            .to(end_date)
+            .include(Weekday::Mon)
+            .include(Weekday::Wed)
+            .include(Weekday::Fri);
+
+        assert_eq!(3, builder.count());
+    }
+
+    #[test]
+    fn builder_include_twice() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("03-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("09-05-2021", format).unwrap();
+
+        let builder = WeekdaysCounterBuilder::default()
+            .from(start_date)
+            .to(end_date)
+            .include(Weekday::Mon)
+            .include(Weekday::Wed)
+            .include(Weekday::Wed)
+            .include(Weekday::Fri)
+            .include(Weekday::Mon);
+
+        assert_eq!(3, builder.count());
+    }
+
+    #[test]
+    fn builder_without_range() {
+        let builder = WeekdaysCounterBuilder::default().include(Weekday::Mon);
+
+        assert_eq!(0, builder.count());
+    }
+
+    #[test]
+    fn different_years() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("01-02-2020", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("01-02-2021", format).unwrap();
+
+        let count = WeekdaysCounter::new(start_date, end_date).count(Weekday::Sun);
+        assert!((52..=53).contains(&count));
+        assert_eq!(53, count);
+    }
+
+    #[test]
+    fn first_match_offsets() {
+        // 01-05-2021 is a Saturday
+        let test_cases = vec![
+            (Some(0), Weekday::Sat),
+            (Some(1), Weekday::Sun),
+            (Some(2), Weekday::Mon),
+            (Some(6), Weekday::Fri),
+        ];
+
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("01-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("30-05-2021", format).unwrap();
+
+        for (expected, weekday) in test_cases {
+            assert_eq!(
+                expected,
+                WeekdaysCounter::new(start_date, end_date).first_match_offset(weekday)
+            );
+        }
+    }
+
+    #[test]
+    fn first_match_offsets_start_weekdays() {
+        // (start date, its weekday, expected offset to the next Sunday)
+        let test_cases = vec![
+            ("03-05-2021", 6), // Monday
+            ("05-05-2021", 4), // Wednesday
+            ("08-05-2021", 1), // Saturday
+            ("09-05-2021", 0), // Sunday
+        ];
+
+        let format = "%d-%m-%Y";
+        let end_date = NaiveDate::parse_from_str("31-05-2021", format).unwrap();
+
+        for (start, expected) in test_cases {
+            let start_date = NaiveDate::parse_from_str(start, format).unwrap();
+            assert_eq!(
+                Some(expected),
+                WeekdaysCounter::new(start_date, end_date).first_match_offset(Weekday::Sun)
+            );
+        }
+    }
+
+    #[test]
+    fn first_match_offsets_out_of_range() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("03-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("05-05-2021", format).unwrap();
+        let counter = WeekdaysCounter::new(start_date, end_date);
+
+        assert_eq!(None, counter.first_match_offset(Weekday::Sun));
+        assert_eq!(
+            None,
+            WeekdaysCounter::new(end_date, start_date).first_match_offset(Weekday::Mon)
+        );
+    }
+
+    #[test]
+    fn normalized() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("01-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("30-05-2021", format).unwrap();
+
+        let counter = WeekdaysCounter::new(start_date, end_date);
+        let swapped = WeekdaysCounter::new(end_date, start_date);
+
+        assert!(!counter.is_inverted());
+        assert!(swapped.is_inverted());
+        assert!(!swapped.normalized().is_inverted());
+
+        for weekday in &[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ] {
+            assert_eq!(counter.count(*weekday), swapped.count(*weekday));
+            assert_eq!(0, swapped.count_strict(*weekday));
+        }
+    }
+
+    #[test]
+    fn iso_week_span() {
+        let test_cases = vec![
+            // a single day
+            (1, "01-05-2021", "01-05-2021"),
+            // Monday to Sunday is exactly one week
+            (1, "03-05-2021", "09-05-2021"),
+            // Sunday to Monday touches two weeks
+            (2, "02-05-2021", "03-05-2021"),
+            (6, "01-05-2021", "31-05-2021"),
+            // 2020-W53 to 2021-W01
+            (2, "31-12-2020", "04-01-2021"),
+            // 2020-W53 (starts in December) to 2021-W02
+            (3, "28-12-2020", "17-01-2021"),
+            // 2019-W01 starts on 31-12-2018
+            (1, "31-12-2018", "06-01-2019"),
+            // inverted range
+            (0, "02-05-2021", "01-05-2021"),
+        ];
+
+        let format = "%d-%m-%Y";
+        for (expected, from, to) in test_cases {
+            let start_date = NaiveDate::parse_from_str(from, format).unwrap();
+            let end_date = NaiveDate::parse_from_str(to, format).unwrap();
+            assert_eq!(
+                expected,
+                WeekdaysCounter::new(start_date, end_date).iso_week_span()
+            );
+        }
+    }
+
+    #[test]
+    fn iso_week_span_year_boundary() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("20-12-2020", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("20-01-2021", format).unwrap();
+
+        let mut weeks = Vec::new();
+        let mut date = start_date;
+        while date <= end_date {
+            let week = (date.iso_week().year(), date.iso_week().week());
+            if !weeks.contains(&week) {
+                weeks.push(week);
+            }
+            date = date.succ_opt().unwrap();
+        }
+
+        assert_eq!(
+            weeks.len() as u32,
+            WeekdaysCounter::new(start_date, end_date).iso_week_span()
+        );
+    }
+
+    #[test]
+    fn multi() {
+        let ranges = [("01-05-2021", "31-05-2021"), ("01-06-2021", "30-06-2021")];
+        assert_eq!(9, count_weekday_multi(&ranges, Weekday::Sun).unwrap());
+        assert_eq!(9, count_weekday_multi(&ranges, Weekday::Mon).unwrap());
+    }
+
+    #[test]
+    fn multi_overlapping() {
+        let ranges = [("01-05-2021", "31-05-2021"), ("30-05-2021", "30-06-2021")];
+        assert_eq!(10, count_weekday_multi(&ranges, Weekday::Sun).unwrap());
+    }
+
+    #[test]
+    fn multi_err() {
+        let ranges = [("01-05-2021", "31-05-2021"), ("01-06-2021", "31-06-2021")];
+        assert!(count_weekday_multi(&ranges, Weekday::Sun).is_err());
+    }
+
+    #[test]
+    fn any_of() {
+        let test_cases = vec![
+            (4, vec![Weekday::Sat, Weekday::Sun]),
+            (6, vec![Weekday::Mon, Weekday::Wed, Weekday::Fri]),
+            (
+                6,
+                vec![Weekday::Mon, Weekday::Wed, Weekday::Mon, Weekday::Fri],
+            ),
+            (0, vec![]),
+        ];
+
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("03-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("16-05-2021", format).unwrap();
+
+        for (expected, days) in test_cases {
+            assert_eq!(
+                expected,
+                WeekdaysCounter::new(start_date, end_date).count_any_of(&days)
+            );
+        }
+    }
+
+    #[test]
+    fn from_range() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("01-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("30-05-2021", format).unwrap();
+
+        assert_eq!(
+            count_sundays(("01-05-2021", "30-05-2021")).unwrap(),
+            WeekdaysCounter::from_range(start_date..=end_date).count(Weekday::Sun)
+        );
+        assert_eq!(
+            5,
+            WeekdaysCounter::from(start_date..=end_date).count(Weekday::Sun)
+        );
+        assert_eq!(
+            1,
+            WeekdaysCounter::from_range(end_date..=end_date).count(Weekday::Sun)
+        );
+
+        // an empty range counts nothing with `count_strict`, the same as the string-based API,
+        // while `count` normalizes it, the same as for the other constructors
+        let counter = WeekdaysCounter::from_range(end_date..=start_date);
+        assert_eq!(
+            count_sundays(("30-05-2021", "01-05-2021")).unwrap(),
+            counter.count_strict(Weekday::Sun)
+        );
+        assert_eq!(0, counter.count_strict(Weekday::Sun));
+        assert_eq!(
+            "30-05-2021..01-05-2021"
+                .parse::<DateRange>()
+                .unwrap()
+                .count(Weekday::Sun),
+            counter.count_strict(Weekday::Sun)
+        );
+        assert_eq!(
+            WeekdaysCounter::new(end_date, start_date).count(Weekday::Sun),
+            counter.count(Weekday::Sun)
+        );
+        assert_eq!(5, counter.count(Weekday::Sun));
+    }
+
+    #[test]
+    fn whole_weeks() {
+        let test_cases = vec![
+            (0, "06-05-2021"),
+            (1, "09-05-2021"),
+            (1, "15-05-2021"),
+            (2, "16-05-2021"),
+        ];
+
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("03-05-2021", format).unwrap();
+
+        for (expected, date_to) in test_cases {
+            let end_date = NaiveDate::parse_from_str(date_to, format).unwrap();
+            let counter = WeekdaysCounter::new(start_date, end_date);
+
+            assert_eq!(expected, counter.whole_weeks());
+            assert_eq!(
+                expected,
+                WeekdaysCounter::new(end_date, start_date).whole_weeks()
+            );
+
+            let max_count = [
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Sat,
+                Weekday::Sun,
+            ]
+            .iter()
+            .map(|&day| counter.count(day))
+            .max()
+            .unwrap();
+            assert!(max_count == expected || max_count == expected + 1);
+        }
+    }
+
+    #[test]
+    fn weekday_tz() {
+        use chrono::{FixedOffset, Utc};
+
+        // Saturday noon to the next Saturday morning in UTC
+        let from = "2021-05-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let to = "2021-05-08T11:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        assert_eq!(1, count_weekday_tz(from, to, Weekday::Sun));
+        assert_eq!(2, count_weekday_tz(from, to, Weekday::Sat));
+
+        // the same instants are Sunday to Sunday at +14:00
+        let kiribati = FixedOffset::east_opt(14 * 3600).unwrap();
+        let from = from.with_timezone(&kiribati);
+        let to = to.with_timezone(&kiribati);
+
+        assert_eq!(2, count_weekday_tz(from, to, Weekday::Sun));
+        assert_eq!(1, count_weekday_tz(from, to, Weekday::Sat));
+    }
+
+    #[test]
+    fn window() {
+        let format = "%d-%m-%Y";
+        let start = NaiveDate::parse_from_str("01-05-2021", format).unwrap();
+
+        let test_cases = vec![
+            (5, Weekday::Sun, 30),
+            (5, Weekday::Mon, 30),
+            (4, Weekday::Tue, 30),
+            (1, Weekday::Sat, 0),
+            (0, Weekday::Sun, 0),
+            // 01-04-2021 to 01-05-2021
+            (4, Weekday::Sun, -30),
+            (5, Weekday::Sat, -30),
+        ];
+
+        for (expected, day, days) in test_cases {
+            assert_eq!(
+                expected,
+                count_weekday_window(start, chrono::Duration::days(days), day)
+            );
+        }
+
+        // the same as the explicit end
+        let end = NaiveDate::parse_from_str("31-05-2021", format).unwrap();
+        assert_eq!(
+            count_sundays(("01-05-2021", "31-05-2021")).unwrap(),
+            count_weekday_window(start, end - start, Weekday::Sun)
+        );
+        // only the whole days count
+        assert_eq!(
+            5,
+            count_weekday_window(start, chrono::Duration::hours(30 * 24 + 23), Weekday::Sun)
+        );
+        assert!(
+            count_weekday_window(start, chrono::Duration::days(1_000_000_000), Weekday::Sun) > 0
+        );
+    }
+
+    #[test]
+    fn week_start() {
+        let format = "%d-%m-%Y";
+        let dates = vec![
+            ("01-05-2021", "30-05-2021"),
+            ("02-05-2021", "02-05-2021"),
+            ("03-05-2021", "08-05-2021"),
+            ("30-05-2021", "01-05-2021"),
+        ];
+        let days = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+
+        for (start_date, end_date) in dates {
+            let start_date = NaiveDate::parse_from_str(start_date, format).unwrap();
+            let end_date = NaiveDate::parse_from_str(end_date, format).unwrap();
+            let counter = WeekdaysCounter::new(start_date, end_date);
+
+            for &day in &days {
+                assert_eq!(
+                    counter.count(day),
+                    counter.count_with_week_start(day, WeekStart::Sun)
+                );
+                assert_eq!(
+                    counter.count(day),
+                    counter.count_with_week_start(day, WeekStart::Mon)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn date_ordinal_locale() {
+        let test_cases = vec![
+            ("1st of May 2021", "May 1st, 2021", "01-05-2021"),
+            ("22nd of May 2021", "May 22nd, 2021", "22-05-2021"),
+            ("13th of June 2021", "June 13th, 2021", "13-06-2021"),
+        ];
+
+        let format = "%d-%m-%Y";
+        for (british, american, input) in test_cases {
+            let date = NaiveDate::parse_from_str(input, format).unwrap();
+            assert_eq!(
+                british,
+                format_date_ordinal_locale(date, DateStyle::British)
+            );
+            assert_eq!(
+                american,
+                format_date_ordinal_locale(date, DateStyle::American)
+            );
+            assert_eq!(american, format_date_with_ordinal(date));
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let format = "%d-%m-%Y";
+        // a Sunday
+        let date = NaiveDate::parse_from_str("02-05-2021", format).unwrap();
+        let counter = WeekdaysCounter::new(date, date);
+
+        assert!(counter.contains(Weekday::Sun));
+        assert!(!counter.contains(Weekday::Mon));
+        assert!(!counter.contains(Weekday::Sat));
+
+        let end_date = NaiveDate::parse_from_str("07-05-2021", format).unwrap();
+        let counter = WeekdaysCounter::new(date, end_date);
+        assert!(counter.contains(Weekday::Fri));
+        assert!(!counter.contains(Weekday::Sat));
+        assert!(WeekdaysCounter::new(end_date, date).contains(Weekday::Fri));
+
+        for day in &[Weekday::Mon, Weekday::Sat, Weekday::Sun] {
+            assert_eq!(counter.count(*day) > 0, counter.contains(*day));
+        }
+    }
+
+    #[test]
+    fn date_with_ordinal() {
+        let test_cases = vec![
+            ("May 1st, 2021", "01-05-2021"),
+            ("May 2nd, 2021", "02-05-2021"),
+            ("May 3rd, 2021", "03-05-2021"),
+            ("May 11th, 2021", "11-05-2021"),
+            ("May 22nd, 2021", "22-05-2021"),
+            ("December 31st, 1999", "31-12-1999"),
+        ];
+
+        let format = "%d-%m-%Y";
+        for (expected, input) in test_cases {
+            let date = NaiveDate::parse_from_str(input, format).unwrap();
+            assert_eq!(expected, format_date_with_ordinal(date));
+        }
+    }
+
+    #[test]
+    fn count_working_days() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("01-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("31-05-2021", format).unwrap();
+        let counter = WeekdaysCounter::new(start_date, end_date);
+
+        assert_eq!(
+            21,
+            counter.count_working_days(&WesternBankingCalendar::default())
+        );
+
+        // the early May and the spring bank holidays in the UK
+        let holidays = vec![
+            NaiveDate::parse_from_str("03-05-2021", format).unwrap(),
+            NaiveDate::parse_from_str("31-05-2021", format).unwrap(),
+            // a Saturday is not a working day anyway
+            NaiveDate::parse_from_str("01-05-2021", format).unwrap(),
+        ];
+        let calendar = WesternBankingCalendar::new(holidays);
+        assert_eq!(19, counter.count_working_days(&calendar));
+        assert_eq!(
+            19,
+            WeekdaysCounter::new(end_date, start_date).count_working_days(&calendar)
+        );
+
+        struct NoWednesdays(WesternBankingCalendar);
+
+        impl Calendar for NoWednesdays {
+            fn is_working_day(&self, date: NaiveDate) -> bool {
+                date.weekday() != Weekday::Wed && self.0.is_working_day(date)
+            }
+        }
+
+        assert_eq!(
+            17,
+            counter.count_working_days(&NoWednesdays(WesternBankingCalendar::default()))
+        );
+        assert_eq!(15, counter.count_working_days(&NoWednesdays(calendar)));
+    }
+
+    #[test]
+    fn count_where() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("01-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("31-05-2021", format).unwrap();
+        let counter = WeekdaysCounter::new(start_date, end_date);
+
+        assert_eq!(15, counter.count_where(|date| date.day() % 2 == 0));
+        assert_eq!(
+            15,
+            WeekdaysCounter::new(end_date, start_date).count_where(|date| date.day() % 2 == 0)
+        );
+        assert_eq!(31, counter.count_where(|_| true));
+        assert_eq!(
+            counter.count(Weekday::Sun),
+            counter.count_where(|date| date.weekday() == Weekday::Sun)
+        );
+    }
+
+    #[test]
+    fn parse_weekdays() {
+        let test_cases = vec![
+            (Some(Weekday::Sun), "Sunday"),
+            (Some(Weekday::Sun), "sun"),
+            (Some(Weekday::Wed), "WED"),
+            (Some(Weekday::Thu), "thursday"),
+            (None, "funday"),
+            (None, "su"),
+            (None, ""),
+        ];
+
+        for (expected, input) in test_cases {
+            assert_eq!(expected, parse_weekday(input));
+        }
+    }
+
+    #[test]
+    fn owned_strings() {
+        let date_from = String::from("01-05-2021");
+        let date_to = String::from("30-05-2021");
+
+        assert_eq!(
+            5,
+            count_sundays((date_from.clone(), date_to.clone())).unwrap()
+        );
+        assert_eq!(
+            count_sundays(("01-05-2021", "30-05-2021")).unwrap(),
+            count_sundays((date_from, date_to)).unwrap()
+        );
+        assert!(count_sundays((String::from("01-05-2021"), String::new())).is_err());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn weekday_time() {
+        use time::{Date, Month};
+
+        let may = |day| Date::from_calendar_date(2021, Month::May, day).unwrap();
+
+        let test_cases = vec![
+            (5, "01-05-2021", "30-05-2021", may(1), may(30)),
+            (3, "03-05-2021", "29-05-2021", may(3), may(29)),
+            (1, "02-05-2021", "02-05-2021", may(2), may(2)),
+            (0, "30-05-2021", "01-05-2021", may(30), may(1)),
+        ];
+
+        for (expected, date_from, date_to, from, to) in test_cases {
+            assert_eq!(
+                expected,
+                count_weekday_time(from, to, time::Weekday::Sunday)
+            );
+            assert_eq!(
+                count_sundays((date_from, date_to)).unwrap(),
+                count_weekday_time(from, to, time::Weekday::Sunday)
+            );
+        }
+    }
+
+    #[test]
+    fn summary() {
+        let format = "%d-%m-%Y";
+        let date = |s| NaiveDate::parse_from_str(s, format).unwrap();
+        let counter = WeekdaysCounter::new(date("01-05-2021"), date("30-05-2021"));
+
+        let test_cases = vec![
+            (5, Some("02-05-2021"), Some("30-05-2021"), Weekday::Sun),
+            (5, Some("01-05-2021"), Some("29-05-2021"), Weekday::Sat),
+            (4, Some("03-05-2021"), Some("24-05-2021"), Weekday::Mon),
+        ];
+
+        for (count, first, last, day) in test_cases {
+            assert_eq!(
+                WeekdaySummary {
+                    count,
+                    first: first.map(date),
+                    last: last.map(date),
+                },
+                counter.summary(day)
+            );
+            assert_eq!(counter.count(day), counter.summary(day).count);
+        }
+
+        let counter = WeekdaysCounter::new(date("03-05-2021"), date("08-05-2021"));
+        assert_eq!(
+            WeekdaySummary {
+                count: 0,
+                first: None,
+                last: None,
+            },
+            counter.summary(Weekday::Sun)
+        );
+
+        let counter = WeekdaysCounter::new(date("30-05-2021"), date("01-05-2021"));
+        assert_eq!(
+            WeekdaySummary {
+                count: 5,
+                first: Some(date("02-05-2021")),
+                last: Some(date("30-05-2021")),
+            },
+            counter.summary(Weekday::Sun)
+        );
+    }
+
+    #[test]
+    fn widest_range() {
+        let counter = WeekdaysCounter::from_range(NaiveDate::MIN..=NaiveDate::MAX);
+        let total_days = (NaiveDate::MAX - NaiveDate::MIN).num_days() as u32 + 1;
+
+        let days = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+        let counts: Vec<u32> = days.iter().map(|&day| counter.count(day)).collect();
+
+        assert_eq!(total_days, counts.iter().sum::<u32>());
+        for &count in &counts {
+            assert!(count == counter.whole_weeks() || count == counter.whole_weeks() + 1);
+        }
+
+        let summary = counter.summary(Weekday::Sun);
+        assert_eq!(Some(Weekday::Sun), summary.first.map(|date| date.weekday()));
+        assert_eq!(Some(Weekday::Sun), summary.last.map(|date| date.weekday()));
+        assert!(counter.iso_week_span() >= counter.whole_weeks());
+        assert!(counter.iso_week_span() <= counter.whole_weeks() + 2);
+    }
+
+    #[test]
+    fn getters() {
+        let format = "%d-%m-%Y";
+        let start_date = NaiveDate::parse_from_str("01-05-2021", format).unwrap();
+        let end_date = NaiveDate::parse_from_str("30-05-2021", format).unwrap();
+
+        let counter = WeekdaysCounter::from_range(start_date..=end_date);
+        assert_eq!(start_date, counter.start());
+        assert_eq!(end_date, counter.end());
+
+        // inverted ranges are kept as is, until normalized
+        let counter = WeekdaysCounter::new(end_date, start_date);
+        assert_eq!(end_date, counter.start());
+        assert_eq!(start_date, counter.end());
+        assert_eq!(start_date, counter.normalized().start());
+
+        assert!(format!("{:?}", counter).contains("2021-05-30"));
+    }
+
+    #[test]
+    fn date_range() {
+        let range = "01-05-2021..30-05-2021".parse::<DateRange>().unwrap();
+
+        let format = "%d-%m-%Y";
+        assert_eq!(
+            NaiveDate::parse_from_str("01-05-2021", format).unwrap(),
+            range.start
+        );
+        assert_eq!(
+            NaiveDate::parse_from_str("30-05-2021", format).unwrap(),
+            range.end
+        );
+        assert_eq!(
+            count_sundays(("01-05-2021", "30-05-2021")).unwrap(),
+            range.count(Weekday::Sun)
+        );
+        assert_eq!(
+            0,
+            "30-05-2021..01-05-2021"
+                .parse::<DateRange>()
+                .unwrap()
+                .count(Weekday::Sun)
+        );
+    }
+
+    #[test]
+    fn date_range_err() {
+        assert_eq!(
+            Err(DateRangeParseError::MissingSeparator),
+            "01-05-2021 30-05-2021".parse::<DateRange>()
+        );
+
+        let test_cases = vec![
+            "01-05-2021..",
+            "..30-05-2021",
+            "01-05-2021..31-02-2021",
+            "01.05.2021..30.05.2021",
+        ];
+        for input in test_cases {
+            assert!(matches!(
+                input.parse::<DateRange>(),
+                Err(DateRangeParseError::InvalidDate(_))
+            ));
+        }
+    }
 }
@@ -0,0 +1,89 @@
+use crate::task_03::{Obfuscatable, Obfuscated};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A credit card number, kept as it was written, incl. the grouping of the digits
+pub struct CreditCard {
+    number: String,
+}
+
+/// Accepts 13 to 19 digits, optionally grouped with spaces or dashes,
+/// e.g. `4111 1111 1111 1111`. The number must pass the Luhn check.
+impl FromStr for CreditCard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars()
+            .any(|c| !c.is_ascii_digit() && c != ' ' && c != '-')
+        {
+            return Err("not a credit card number".into());
+        }
+
+        let digits: Vec<u32> = s.chars().filter_map(|c| c.to_digit(10)).collect();
+        if digits.len() < 13 || digits.len() > 19 {
+            return Err("credit card number must have from 13 to 19 digits".into());
+        }
+
+        if !luhn_check(&digits) {
+            return Err("credit card number doesn't pass the Luhn check".into());
+        }
+
+        Ok(CreditCard { number: s.into() })
+    }
+}
+
+/// Every second digit from the right is doubled, the sum of all digits must be divisible by 10
+fn luhn_check(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| {
+            if i % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum();
+
+    sum % 10 == 0
+}
+
+impl Obfuscatable for CreditCard {}
+
+impl Obfuscated<CreditCard> {
+    /// Returns a number of digits that are masked, all but the last 4
+    pub fn masked_char_count(&self) -> usize {
+        let digits = self.0.number.chars().filter(|c| c.is_ascii_digit()).count();
+        digits.saturating_sub(4)
+    }
+}
+
+impl Display for Obfuscated<CreditCard> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // the same approach as for phone numbers, the last digits are written first
+        let number_of_visible = 4;
+        let mut visible = 0;
+        let mut output = String::with_capacity(self.0.number.len());
+
+        for ch in self.0.number.chars().rev() {
+            if ch.is_ascii_digit() && visible >= number_of_visible {
+                output.push('*');
+            } else {
+                if ch.is_ascii_digit() {
+                    visible += 1;
+                }
+                output.push(ch);
+            }
+        }
+
+        write!(f, "{}", output.chars().rev().collect::<String>())
+    }
+}
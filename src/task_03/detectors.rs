@@ -0,0 +1,252 @@
+use crate::task_03::credit_cards::CreditCard;
+use crate::task_03::emails::Email;
+use crate::task_03::ip_addresses::IpAddress;
+use crate::task_03::mac_addresses::MacAddress;
+use crate::task_03::phone_numbers;
+use crate::task_03::phone_numbers::PhoneNumber;
+use crate::task_03::ssns::Ssn;
+use crate::task_03::{InputKind, Obfuscatable, ObfuscationError, ObfuscationOptions};
+
+/// Recognizes a type of input and obfuscates it
+///
+/// Returns `None` if the input is not recognized, so the next detector could be tried
+pub trait Detector {
+    fn try_obfuscate(&self, input: &str) -> Option<String>;
+
+    /// The kind of input this detector recognizes
+    fn kind(&self) -> InputKind {
+        InputKind::Custom
+    }
+}
+
+/// Built-in detector for emails
+pub struct EmailDetector;
+
+impl Detector for EmailDetector {
+    fn try_obfuscate(&self, input: &str) -> Option<String> {
+        let parsed_email = input.parse::<Email>().ok()?;
+        Some(parsed_email.obfuscated().to_string())
+    }
+
+    fn kind(&self) -> InputKind {
+        InputKind::Email
+    }
+}
+
+/// Built-in detector for credit card numbers
+pub struct CreditCardDetector;
+
+impl Detector for CreditCardDetector {
+    fn try_obfuscate(&self, input: &str) -> Option<String> {
+        let parsed_card = input.parse::<CreditCard>().ok()?;
+        Some(parsed_card.obfuscated().to_string())
+    }
+
+    fn kind(&self) -> InputKind {
+        InputKind::CreditCard
+    }
+}
+
+/// Built-in detector for IP addresses
+pub struct IpAddressDetector;
+
+impl Detector for IpAddressDetector {
+    fn try_obfuscate(&self, input: &str) -> Option<String> {
+        let parsed_ip = input.parse::<IpAddress>().ok()?;
+        Some(parsed_ip.obfuscated().to_string())
+    }
+
+    fn kind(&self) -> InputKind {
+        InputKind::IpAddress
+    }
+}
+
+/// Built-in detector for US Social Security Numbers
+pub struct SsnDetector;
+
+impl Detector for SsnDetector {
+    fn try_obfuscate(&self, input: &str) -> Option<String> {
+        let parsed_ssn = input.parse::<Ssn>().ok()?;
+        Some(parsed_ssn.obfuscated().to_string())
+    }
+
+    fn kind(&self) -> InputKind {
+        InputKind::Ssn
+    }
+}
+
+/// Built-in detector for MAC addresses
+pub struct MacAddressDetector;
+
+impl Detector for MacAddressDetector {
+    fn try_obfuscate(&self, input: &str) -> Option<String> {
+        let parsed_mac = input.parse::<MacAddress>().ok()?;
+        Some(parsed_mac.obfuscated().to_string())
+    }
+
+    fn kind(&self) -> InputKind {
+        InputKind::MacAddress
+    }
+}
+
+/// Built-in detector for phone numbers
+///
+/// The numbers with fewer than `min_digits` digits are not recognized, so short numeric
+/// sequences, e.g. `1 2`, are not taken for phone numbers
+#[derive(Copy, Clone, Debug)]
+pub struct PhoneDetector {
+    pub min_digits: usize,
+}
+
+impl Default for PhoneDetector {
+    fn default() -> Self {
+        PhoneDetector {
+            min_digits: phone_numbers::MIN_DIGITS,
+        }
+    }
+}
+
+impl Detector for PhoneDetector {
+    fn try_obfuscate(&self, input: &str) -> Option<String> {
+        let parsed_phone = input.parse::<PhoneNumber>().ok()?;
+        if parsed_phone.digit_count() < self.min_digits {
+            return None;
+        }
+        Some(parsed_phone.obfuscated().to_string())
+    }
+
+    fn kind(&self) -> InputKind {
+        InputKind::Phone
+    }
+}
+
+/// An ordered list of detectors, the first one that recognizes the input wins
+///
+/// The default registry contains the built-in detectors, the custom ones could be appended:
+///
+/// ```rust
+/// let detectors = DetectorRegistry::default().with(IbanDetector);
+/// let obfuscated = detectors.obfuscate("GB82 WEST 1234 5698 7654 32").unwrap();
+/// ```
+pub struct DetectorRegistry(Vec<Box<dyn Detector>>);
+
+impl Default for DetectorRegistry {
+    fn default() -> Self {
+        // credit card numbers, IPv4 addresses, SSNs and MAC addresses of digits only
+        // are also valid phone numbers, hence they go first
+        DetectorRegistry(vec![
+            Box::new(EmailDetector),
+            Box::new(CreditCardDetector),
+            Box::new(IpAddressDetector),
+            Box::new(SsnDetector),
+            Box::new(MacAddressDetector),
+            Box::new(PhoneDetector::default()),
+        ])
+    }
+}
+
+impl DetectorRegistry {
+    /// Appends a detector to the end of the list
+    pub fn with<D: Detector + 'static>(mut self, detector: D) -> Self {
+        self.push(Box::new(detector));
+        self
+    }
+
+    /// Appends a detector to the end of the list
+    pub fn push(&mut self, detector: Box<dyn Detector>) {
+        self.0.push(detector);
+    }
+
+    /// Moves a detector to the start of the list, so it's tried before the others.
+    /// The detectors of the same built-in kind are replaced, the custom ones are kept.
+    pub fn push_front(&mut self, detector: Box<dyn Detector>) {
+        self.remove_builtin(detector.kind());
+        self.0.insert(0, detector);
+    }
+
+    /// Moves a detector to the end of the list, so it's tried after the others.
+    /// The detectors of the same built-in kind are replaced, the custom ones are kept.
+    pub fn push_back(&mut self, detector: Box<dyn Detector>) {
+        self.remove_builtin(detector.kind());
+        self.push(detector);
+    }
+
+    /// Removes all the detectors of the given `kind`
+    pub fn remove(&mut self, kind: InputKind) {
+        self.0.retain(|detector| detector.kind() != kind);
+    }
+
+    /// The same as `remove`, but there may be many custom detectors, so they are never removed
+    fn remove_builtin(&mut self, kind: InputKind) {
+        if kind != InputKind::Custom {
+            self.remove(kind);
+        }
+    }
+
+    /// Iterates over the detectors in the order they are tried
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Detector> {
+        self.0.iter().map(|detector| detector.as_ref())
+    }
+
+    /// Obfuscates the input with the first detector that recognizes it
+    pub fn obfuscate(&self, input: &str) -> Result<String, ObfuscationError> {
+        let (_kind, obfuscated) = self.obfuscate_typed(input)?;
+        Ok(obfuscated)
+    }
+
+    /// Obfuscates the input with the detectors of the given `kind` only, i.e. the input is forced
+    /// to be interpreted as that kind
+    pub fn obfuscate_as(&self, input: &str, kind: InputKind) -> Result<String, ObfuscationError> {
+        self.0
+            .iter()
+            .filter(|detector| detector.kind() == kind)
+            .find_map(|detector| detector.try_obfuscate(input))
+            .ok_or(ObfuscationError::UnknownInput)
+    }
+
+    /// The same as `obfuscate`, but with the given `options` applied: the phone numbers keep
+    /// `visible` trailing digits, the domains of the emails are masked if `domain` is set,
+    /// and the hidden characters are replaced with `mask`, incl. the ones of the custom detectors
+    pub fn obfuscate_with_options(
+        &self,
+        input: &str,
+        options: ObfuscationOptions,
+    ) -> Result<String, ObfuscationError> {
+        let (kind, obfuscated) = self.obfuscate_typed(input)?;
+
+        let obfuscated = match kind {
+            InputKind::Email => match input.parse::<Email>() {
+                // the local part may contain a visible `*`, so the mask is built from the segments
+                Ok(email) => {
+                    let segments = email.obfuscated_with(options).segments();
+                    return Ok(format!(
+                        "{}{}{}@{}",
+                        segments.visible_prefix,
+                        options.mask.to_string().repeat(segments.masked_len),
+                        segments.visible_suffix,
+                        segments.domain.replace('*', &options.mask.to_string())
+                    ));
+                }
+                Err(_) => obfuscated,
+            },
+            InputKind::Phone => match input.parse::<PhoneNumber>() {
+                Ok(phone) => phone.obfuscated_with(options).to_string(),
+                Err(_) => obfuscated,
+            },
+            _ => obfuscated,
+        };
+
+        Ok(obfuscated.replace('*', &options.mask.to_string()))
+    }
+
+    /// The same as `obfuscate`, but also returns the kind of the recognized input
+    pub fn obfuscate_typed(&self, input: &str) -> Result<(InputKind, String), ObfuscationError> {
+        self.0
+            .iter()
+            .find_map(|detector| {
+                let obfuscated = detector.try_obfuscate(input)?;
+                Some((detector.kind(), obfuscated))
+            })
+            .ok_or(ObfuscationError::UnknownInput)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::task_03::{Obfuscatable, Obfuscated};
+use std::convert::TryFrom;
 use std::fmt;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Display, Formatter, Write};
 use std::str::FromStr;
 
 /// This is a simplified representation of the email address, but it's enough for the purposes
@@ -8,52 +9,533 @@ use std::str::FromStr;
 pub struct Email {
     local: String,
     domain: String,
+    mask_domain: bool,
+    public_suffix_aware: bool,
+    keep_plus_tag: bool,
+    structural_mask: bool,
+    options: EmailObfuscationOptions,
 }
 
+/// A mode that changes the way an email is obfuscated, see `Email::obfuscated_with`
+pub trait EmailMode {
+    fn apply(self, email: &mut Email);
+}
+
+/// Obfuscation mode that also masks the second-level label of the domain,
+/// e.g. `local@domain.com` => `l***l@d****n.com`
+#[derive(Copy, Clone, Debug)]
+pub struct ObfuscateDomain;
+
+impl EmailMode for ObfuscateDomain {
+    fn apply(self, email: &mut Email) {
+        email.mask_domain = true;
+    }
+}
+
+/// Obfuscation mode that masks the registrable label of the domain, i.e. the one right before
+/// the public suffix, e.g. `local@sub.domain.co.uk` => `l***l@sub.d****n.co.uk`
+///
+/// Only the common multi-part suffixes are recognized, see `MULTI_PART_SUFFIXES`,
+/// otherwise the last label is treated as the suffix, the same as in `ObfuscateDomain`
+#[derive(Copy, Clone, Debug)]
+pub struct ObfuscateRegistrableDomain;
+
+impl EmailMode for ObfuscateRegistrableDomain {
+    fn apply(self, email: &mut Email) {
+        email.mask_domain = true;
+        email.public_suffix_aware = true;
+    }
+}
+
+/// Public suffixes of two labels that are recognized by `ObfuscateRegistrableDomain`.
+///
+/// This is a small subset of the Public Suffix List, only the commonly used suffixes are here
+pub const MULTI_PART_SUFFIXES: [&str; 14] = [
+    "co.uk", "org.uk", "ac.uk", "gov.uk", "co.jp", "ne.jp", "com.au", "net.au", "org.au", "co.nz",
+    "com.br", "co.in", "co.za", "com.cn",
+];
+
+/// A number of mask characters that replace the hidden part of the local part when
+/// `EmailObfuscationOptions::hide_length` is set, regardless of its length
+pub const HIDDEN_LENGTH_MASK: usize = 5;
+
+/// A number of characters of the local part that are left visible at its start and its end,
+/// e.g. `jo*****th@example.com` for 2 and 2. The default is 1 and 1.
+///
+/// The visible characters never overlap, so the local part is never revealed more than once.
+/// A quoted local part, e.g. `"a@b"`, keeps only its quotes visible.
+///
+/// `min_masked` is a minimum number of mask characters in the local part, short local parts are
+/// padded with them, e.g. `ab@x.com` => `a***b@x.com` for 3. The default is 0, i.e. no padding.
+///
+/// `fixed_width` guarantees that the obfuscated email has exactly as many characters as
+/// the original one, i.e. every hidden character is replaced by a single mask character,
+/// `min_masked` is ignored then. The default is `false`.
+///
+/// `hide_length` replaces the hidden characters with exactly `HIDDEN_LENGTH_MASK` mask
+/// characters, so the length of the local part is not revealed, e.g. both `abc@x.com` and
+/// `abcdefghijk@x.com` => `a*****c@x.com` and `a*****k@x.com`. `min_masked` is ignored then,
+/// while `fixed_width` takes precedence. The default is `false`, i.e. every hidden character
+/// is replaced by a single mask character.
+///
+/// `reveal_two_char` leaves both characters of a 2-character local part visible, e.g. `ab@x.com`
+/// stays as is. Otherwise, the second one is masked, e.g. `a*@x.com`. The default is `true`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct EmailObfuscationOptions {
+    pub visible_head: usize,
+    pub visible_tail: usize,
+    pub min_masked: usize,
+    pub fixed_width: bool,
+    pub reveal_two_char: bool,
+    pub hide_length: bool,
+}
+
+impl Default for EmailObfuscationOptions {
+    fn default() -> Self {
+        EmailObfuscationOptions {
+            visible_head: 1,
+            visible_tail: 1,
+            min_masked: 0,
+            fixed_width: false,
+            reveal_two_char: true,
+            hide_length: false,
+        }
+    }
+}
+
+impl EmailMode for EmailObfuscationOptions {
+    fn apply(self, email: &mut Email) {
+        email.options = self;
+    }
+}
+
+/// Obfuscation mode that keeps the sub-address visible, i.e. everything after the first `+`
+/// in the local part, e.g. `john.doe+news@gmail.com` => `j******e+news@gmail.com`
+#[derive(Copy, Clone, Debug)]
+pub struct KeepPlusTag;
+
+impl EmailMode for KeepPlusTag {
+    fn apply(self, email: &mut Email) {
+        email.keep_plus_tag = true;
+    }
+}
+
+/// Obfuscation mode that keeps the shape of the hidden characters, i.e. the digits are replaced
+/// by `#`, the letters by `x` and the rest by `*`, e.g. `abc123@x.com` => `axx##3@x.com`.
+/// It's meant for the test snapshots, so the diffs are meaningful.
+///
+/// Every hidden character has its placeholder, so `min_masked` and `hide_length` are ignored.
+#[derive(Copy, Clone, Debug)]
+pub struct StructuralMask;
+
+impl EmailMode for StructuralMask {
+    fn apply(self, email: &mut Email) {
+        email.structural_mask = true;
+    }
+}
+
+/// An error returned when an email couldn't be parsed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EmailParseError {
+    /// The input has no `@` between the local part and the domain
+    NotAnEmail,
+    /// The input has more than one unquoted `@`, e.g. `a@b@c.com`
+    MultipleAtSigns,
+    /// The quoted local part is not closed, e.g. `"a@b.com`
+    MissingClosingQuote,
+    EmptyLocalPart,
+    EmptyDomain,
+    DomainWithoutDots,
+    /// The local part starts or ends with a dot, e.g. `.a@b.com`
+    LocalPartDot,
+    /// The domain starts or ends with a dot, e.g. `a@b.com.`
+    DomainDot,
+}
+
+impl Display for EmailParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            EmailParseError::NotAnEmail => "not an email",
+            EmailParseError::MultipleAtSigns => "email has more than one @",
+            EmailParseError::MissingClosingQuote => "email local part has no closing quote",
+            EmailParseError::EmptyLocalPart => "email local part is empty",
+            EmailParseError::EmptyDomain => "email domain is empty",
+            EmailParseError::DomainWithoutDots => "email domain has no dots",
+            EmailParseError::LocalPartDot => "email local part starts or ends with a dot",
+            EmailParseError::DomainDot => "email domain starts or ends with a dot",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for EmailParseError {}
+
 /// This is not a truly correct parser for an email.
 ///
 /// It's not that easy to parse an email address. One tries to parse it with regexes. Although,
 /// it could be a decent solution, it won't cover the Internet Message Format RFCs.
 ///
-/// Hence, I won't validate an address here, just parse it to easy the life
+/// Hence, I only do some basic checks here: both parts must be non-empty, must not start
+/// or end with a dot, and the domain must contain at least one dot.
+/// The leading and trailing whitespace is ignored. A quoted local part, e.g. `"a@b"`,
+/// is taken as is, including the quotes.
 impl FromStr for Email {
-    type Err = String;
+    type Err = EmailParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split('@').collect();
+        let s = s.trim();
+
+        // a quoted local part may contain `@`, e.g. `"a@b"@example.com`, hence the address
+        // is split on the `@` right after the closing quote
+        let (local, domain) = match s.strip_prefix('"') {
+            Some(quoted) => {
+                let closing_quote = quoted
+                    .find('"')
+                    .ok_or(EmailParseError::MissingClosingQuote)?;
+                let domain = quoted[closing_quote + 1..]
+                    .strip_prefix('@')
+                    .ok_or(EmailParseError::NotAnEmail)?;
+                (&s[..closing_quote + 2], domain)
+            }
+            None => s.split_once('@').ok_or(EmailParseError::NotAnEmail)?,
+        };
 
-        if parts.len() != 2 {
-            return Err("not an email".into());
+        if domain.contains('@') {
+            return Err(EmailParseError::MultipleAtSigns);
+        }
+        if local.is_empty() {
+            return Err(EmailParseError::EmptyLocalPart);
+        }
+        if domain.is_empty() {
+            return Err(EmailParseError::EmptyDomain);
+        }
+        if !domain.contains('.') {
+            return Err(EmailParseError::DomainWithoutDots);
+        }
+        if local.starts_with('.') || local.ends_with('.') {
+            return Err(EmailParseError::LocalPartDot);
+        }
+        if domain.starts_with('.') || domain.ends_with('.') {
+            return Err(EmailParseError::DomainDot);
         }
 
         Ok(Email {
-            local: parts[0].into(),
-            domain: parts[1].into(),
+            local: local.into(),
+            domain: domain.into(),
+            mask_domain: false,
+            public_suffix_aware: false,
+            keep_plus_tag: false,
+            structural_mask: false,
+            options: EmailObfuscationOptions::default(),
         })
     }
 }
 
+impl TryFrom<&str> for Email {
+    type Error = <Email as FromStr>::Err;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 impl Obfuscatable for Email {}
 
-impl Display for Obfuscated<Email> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let chars = self.0.local.chars();
-        if let Some(c) = chars.clone().next() {
-            write!(f, "{}", c)?;
+impl Email {
+    /// The same as `obfuscated()`, but with the given obfuscation mode applied
+    pub fn obfuscated_with<M: EmailMode>(mut self, mode: M) -> Obfuscated<Self> {
+        mode.apply(&mut self);
+        self.obfuscated()
+    }
+
+    /// Obfuscates the local part with a number of asterisks that doesn't depend on its length
+    ///
+    /// The number of asterisks is between 3 and 8, it is picked pseudo-randomly from
+    /// the `seed` and the address itself, so the same record is always masked the same way.
+    pub fn obfuscated_length_hidden(&self, seed: u64) -> String {
+        let mut chars = self.local.chars();
+        let first = chars.next();
+        let last = chars.last();
+
+        let stars = 3 + (self.salted_hash(seed) % 6) as usize;
+
+        let mut output = String::new();
+        output.extend(first);
+        output.push_str(&"*".repeat(stars));
+        output.extend(last);
+        output.push('@');
+        output.push_str(&self.domain);
+        output
+    }
+
+    /// FNV-1a over the address, finalized with the splitmix64 mixer.
+    ///
+    /// `std::collections::hash_map::DefaultHasher` is not guaranteed to be stable between
+    /// Rust releases, hence the hand written hash.
+    fn salted_hash(&self, seed: u64) -> u64 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325 ^ seed;
+        for byte in self
+            .local
+            .bytes()
+            .chain(b"@".iter().copied())
+            .chain(self.domain.bytes())
+        {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^ (hash >> 31)
+    }
+}
+
+/// Structured result of the email obfuscation.
+///
+/// The obfuscated email is `visible_prefix`, followed by `masked_len` asterisks,
+/// `visible_suffix`, `@` and the `domain` (which is masked as well in `ObfuscateDomain` mode)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ObfuscatedSegments {
+    pub visible_prefix: String,
+    pub masked_len: usize,
+    pub visible_suffix: String,
+    pub domain: String,
+}
+
+/// Splits the `part` into `head` visible characters, a number of hidden characters,
+/// and `tail` visible characters
+///
+/// The string is walked once to count the characters, the tail is found from the back,
+/// the visible parts are the slices of the `part`, so nothing is allocated
+fn mask_part(part: &str, head: usize, tail: usize) -> (&str, usize, &str) {
+    let mut len = 0;
+    let mut head_end = part.len();
+    for (i, _) in part.char_indices() {
+        if len == head {
+            head_end = i;
+        }
+        len += 1;
+    }
+
+    let head = head.min(len);
+    let tail = tail.min(len - head);
+    let tail_start = match tail {
+        0 => part.len(),
+        _ => part
+            .char_indices()
+            .rev()
+            .nth(tail - 1)
+            .map_or(part.len(), |(i, _)| i),
+    };
+
+    (&part[..head_end], len - head - tail, &part[tail_start..])
+}
+
+/// Writes `len` mask characters
+fn write_mask<W: Write>(w: &mut W, len: usize) -> fmt::Result {
+    for _ in 0..len {
+        w.write_char('*')?;
+    }
+    Ok(())
+}
+
+/// Writes a placeholder of the same class for every `hidden` character, see `StructuralMask`
+fn write_structural_mask<W: Write>(w: &mut W, hidden: &str) -> fmt::Result {
+    for ch in hidden.chars() {
+        let placeholder = if ch.is_ascii_digit() {
+            '#'
+        } else if ch.is_alphabetic() {
+            'x'
+        } else {
+            '*'
+        };
+        w.write_char(placeholder)?;
+    }
+    Ok(())
+}
+
+impl Obfuscated<Email> {
+    /// Checks if the `original` email would be obfuscated to the same output,
+    /// with the same obfuscation modes applied
+    pub fn matches_plaintext(&self, original: &str) -> bool {
+        match original.parse::<Email>() {
+            Ok(mut email) => {
+                email.mask_domain = self.0.mask_domain;
+                email.public_suffix_aware = self.0.public_suffix_aware;
+                email.keep_plus_tag = self.0.keep_plus_tag;
+                email.structural_mask = self.0.structural_mask;
+                email.options = self.0.options;
+                email.obfuscated().to_string() == self.to_string()
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Returns a number of characters of the local part that are masked. Unlike
+    /// `segments().masked_len`, the padding of `min_masked` is not counted.
+    pub fn masked_char_count(&self) -> usize {
+        let total = self.0.local.chars().count();
+        let (prefix, _, suffix, tag) = self.local_segments();
+        // the plus sign is visible as well
+        let visible = prefix.chars().count()
+            + suffix.chars().count()
+            + tag.map_or(0, |tag| tag.chars().count() + 1);
+
+        total - visible
+    }
+
+    /// Returns the segments of the obfuscated email, i.e. what's visible and what's masked
+    pub fn segments(&self) -> ObfuscatedSegments {
+        let (prefix, masked_len, suffix, tag) = self.local_segments();
+
+        let visible_suffix = match tag {
+            Some(tag) => format!("{}+{}", suffix, tag),
+            None => suffix.to_string(),
+        };
+
+        let mut domain = String::with_capacity(self.0.domain.len());
+        self.write_domain(&mut domain)
+            .expect("writing into a String doesn't fail");
+
+        ObfuscatedSegments {
+            visible_prefix: prefix.to_string(),
+            masked_len,
+            visible_suffix,
+            domain,
+        }
+    }
+
+    /// Writes the obfuscated email into `w`, without allocating an intermediate `String`
+    pub fn write_to<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let (prefix, masked_len, suffix, tag) = self.local_segments();
+
+        w.write_str(prefix)?;
+        if self.0.structural_mask {
+            // the visible parts are the slices of the local part, or of its base if there is a tag
+            let base_len = tag.map_or(self.0.local.len(), |tag| self.0.local.len() - tag.len() - 1);
+            write_structural_mask(w, &self.0.local[prefix.len()..base_len - suffix.len()])?;
+        } else {
+            write_mask(w, masked_len)?;
+        }
+        w.write_str(suffix)?;
+        if let Some(tag) = tag {
+            w.write_char('+')?;
+            w.write_str(tag)?;
+        }
+        w.write_char('@')?;
+        self.write_domain(w)
+    }
+
+    /// Returns the fraction of the local part that is left visible, from 0 to 1,
+    /// e.g. 2/3 for `abc@x.com` => `a*c@x.com`. The padding of `min_masked` is not counted.
+    pub fn reveal_ratio(&self) -> f32 {
+        let total = self.0.local.chars().count();
+        let visible = total - self.masked_char_count();
+
+        visible as f32 / total as f32
+    }
+
+    /// Returns the visible prefix, the number of masked characters, the visible suffix
+    /// and the plus tag of the local part, if it's kept
+    fn local_segments(&self) -> (&str, usize, &str, Option<&str>) {
+        let EmailObfuscationOptions {
+            visible_head,
+            visible_tail,
+            min_masked,
+            fixed_width,
+            reveal_two_char,
+            hide_length,
+        } = self.0.options;
+
+        // a number of the visible characters at the start and at the end of the part
+        let visible = |part: &str| {
+            if !reveal_two_char && part.chars().count() == 2 {
+                (visible_head.min(1), 0)
+            } else {
+                (visible_head, visible_tail)
+            }
+        };
+
+        // a quoted local part is masked as a whole, it has no sub-address, and only the quotes
+        // are visible, whatever `visible_head` and `visible_tail` are
+        let is_quoted = self.0.local.starts_with('"');
+        let (prefix, masked_len, suffix, tag) = match self.0.local.split_once('+') {
+            _ if is_quoted => {
+                let (prefix, masked_len, suffix) = mask_part(&self.0.local, 1, 1);
+                (prefix, masked_len, suffix, None)
+            }
+            Some((base, tag)) if self.0.keep_plus_tag => {
+                let (head, tail) = visible(base);
+                let (prefix, masked_len, suffix) = mask_part(base, head, tail);
+                (prefix, masked_len, suffix, Some(tag))
+            }
+            _ => {
+                let (head, tail) = visible(&self.0.local);
+                let (prefix, masked_len, suffix) = mask_part(&self.0.local, head, tail);
+                (prefix, masked_len, suffix, None)
+            }
+        };
+
+        if fixed_width || self.0.structural_mask {
+            (prefix, masked_len, suffix, tag)
+        } else if hide_length {
+            (prefix, HIDDEN_LENGTH_MASK, suffix, tag)
+        } else {
+            (prefix, masked_len.max(min_masked), suffix, tag)
         }
+    }
 
-        let len = chars.clone().count();
+    /// Checks if the domain ends with one of the `MULTI_PART_SUFFIXES`, the case is ignored
+    fn has_multi_part_suffix(&self) -> bool {
+        let mut labels = self.0.domain.rsplit('.');
+        let (top_level, second_level) = match (labels.next(), labels.next()) {
+            (Some(top_level), Some(second_level)) => (top_level, second_level),
+            _ => return false,
+        };
 
-        if len > 2 {
-            write!(f, "*****")?;
+        MULTI_PART_SUFFIXES
+            .iter()
+            .any(|suffix| match suffix.split_once('.') {
+                Some((suffix_second, suffix_top)) => {
+                    second_level.eq_ignore_ascii_case(suffix_second)
+                        && top_level.eq_ignore_ascii_case(suffix_top)
+                }
+                None => false,
+            })
+    }
+
+    fn write_domain<W: Write>(&self, w: &mut W) -> fmt::Result {
+        if !self.0.mask_domain {
+            return w.write_str(&self.0.domain);
         }
 
-        if len > 1 {
-            if let Some(c) = chars.last() {
-                write!(f, "{}", c)?;
+        // only the label before the suffix is masked, e.g. `domain` in `mail.domain.com`
+        let labels = self.0.domain.split('.').count();
+        let suffix_labels = if self.0.public_suffix_aware && self.has_multi_part_suffix() {
+            2
+        } else {
+            1
+        };
+        let masked_label = labels.checked_sub(suffix_labels + 1);
+        for (i, label) in self.0.domain.split('.').enumerate() {
+            if i > 0 {
+                w.write_char('.')?;
+            }
+            if Some(i) == masked_label {
+                let (prefix, masked_len, suffix) = mask_part(label, 1, 1);
+                w.write_str(prefix)?;
+                write_mask(w, masked_len)?;
+                w.write_str(suffix)?;
+            } else {
+                w.write_str(label)?;
             }
         }
+        Ok(())
+    }
+}
 
-        write!(f, "@{}", self.0.domain)
+impl Display for Obfuscated<Email> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
     }
 }
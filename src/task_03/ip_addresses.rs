@@ -0,0 +1,55 @@
+use crate::task_03::{Obfuscatable, Obfuscated};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// An IPv4 or IPv6 address
+pub struct IpAddress(IpAddr);
+
+/// Unlike emails and phone numbers, IP addresses have a strict format, so the std parser is used
+impl FromStr for IpAddress {
+    type Err = std::net::AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(IpAddress(s.parse()?))
+    }
+}
+
+impl Obfuscatable for IpAddress {}
+
+impl Obfuscated<IpAddress> {
+    /// Returns a number of characters of the host part that are masked, e.g. 1 for `1.2.3.4`,
+    /// while the mask is always `***`. IPv6 segments are counted as written by `{:x}`.
+    pub fn masked_char_count(&self) -> usize {
+        match self.0 .0 {
+            IpAddr::V4(ip) => ip.octets()[3].to_string().len(),
+            IpAddr::V6(ip) => ip.segments()[4..]
+                .iter()
+                .map(|segment| format!("{:x}", segment).len())
+                .sum(),
+        }
+    }
+}
+
+/// The network part is kept, and the host part is masked:
+/// the last octet of IPv4, e.g. `192.168.1.***`, and the last 64 bits of IPv6,
+/// e.g. `2001:db8:85a3:8d3:****:****:****:****`
+impl Display for Obfuscated<IpAddress> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 .0 {
+            IpAddr::V4(ip) => {
+                let octets = ip.octets();
+                write!(f, "{}.{}.{}.***", octets[0], octets[1], octets[2])
+            }
+            IpAddr::V6(ip) => {
+                let segments = ip.segments();
+                write!(
+                    f,
+                    "{:x}:{:x}:{:x}:{:x}:****:****:****:****",
+                    segments[0], segments[1], segments[2], segments[3]
+                )
+            }
+        }
+    }
+}
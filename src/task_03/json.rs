@@ -0,0 +1,59 @@
+use crate::task_03::{DetectorRegistry, InputKind};
+use chrono::NaiveDate;
+use serde_json::Value;
+
+/// Obfuscates the string values of a JSON document, the structure is kept as is
+///
+/// The objects and the arrays are walked recursively, every string that is an email or a phone
+/// number is replaced by its obfuscated form. The other values, incl. the other kinds of input
+/// recognized by `obfuscate`, e.g. IP addresses, as well as the keys of the objects,
+/// are left untouched.
+///
+/// The ISO-8601 dates, e.g. `2021-05-01`, are groups of digits as well, so they are never
+/// taken for phone numbers here.
+///
+/// Usage example:
+///
+/// ```rust
+/// let value = serde_json::json!({"user": {"email": "local@domain.com"}});
+/// let obfuscated = obfuscate_json(&value);
+/// println!("{}", obfuscated); // prints {"user":{"email":"l***l@domain.com"}}
+/// ```
+pub fn obfuscate_json(value: &Value) -> Value {
+    obfuscate_value(&DetectorRegistry::default(), value)
+}
+
+fn obfuscate_value(detectors: &DetectorRegistry, value: &Value) -> Value {
+    match value {
+        Value::String(s) => {
+            Value::String(obfuscate_string(detectors, s).unwrap_or_else(|| s.clone()))
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| obfuscate_value(detectors, value))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), obfuscate_value(detectors, value)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Obfuscates the string if it's an email or a phone number, returns `None` otherwise
+///
+/// The kind is detected with the usual precedence, so e.g. an IP address is not taken
+/// for a phone number
+fn obfuscate_string(detectors: &DetectorRegistry, s: &str) -> Option<String> {
+    if s.parse::<NaiveDate>().is_ok() {
+        return None;
+    }
+
+    match detectors.obfuscate_typed(s) {
+        Ok((InputKind::Email, obfuscated)) | Ok((InputKind::Phone, obfuscated)) => Some(obfuscated),
+        _ => None,
+    }
+}
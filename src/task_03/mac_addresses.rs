@@ -0,0 +1,59 @@
+use crate::task_03::{Obfuscatable, Obfuscated};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// MAC address, e.g. `AA:BB:CC:DD:EE:FF` or `aa-bb-cc-dd-ee-ff`
+pub struct MacAddress {
+    octets: Vec<String>,
+    separator: char,
+}
+
+/// Six octets of two hex digits each, separated by either colons or dashes, but not both.
+/// The case of the digits is kept as is.
+impl FromStr for MacAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let separator = if s.contains(':') { ':' } else { '-' };
+        let octets: Vec<&str> = s.split(separator).collect();
+
+        let is_valid = octets.len() == 6
+            && octets
+                .iter()
+                .all(|octet| octet.len() == 2 && octet.chars().all(|c| c.is_ascii_hexdigit()));
+
+        if !is_valid {
+            return Err("not a MAC address".into());
+        }
+
+        Ok(MacAddress {
+            octets: octets.into_iter().map(String::from).collect(),
+            separator,
+        })
+    }
+}
+
+impl Obfuscatable for MacAddress {}
+
+impl Obfuscated<MacAddress> {
+    /// Returns a number of hex digits that are masked, i.e. the device part
+    pub fn masked_char_count(&self) -> usize {
+        self.0.octets[3..].iter().map(|octet| octet.len()).sum()
+    }
+}
+
+/// The first three octets (the OUI, i.e. the vendor) are kept, the device part is masked
+impl Display for Obfuscated<MacAddress> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let separator = self.0.separator;
+        write!(
+            f,
+            "{}{}**{}**{}**",
+            self.0.octets[..3].join(&separator.to_string()),
+            separator,
+            separator,
+            separator
+        )
+    }
+}
@@ -0,0 +1,120 @@
+use crate::task_03::emails::{Email, EmailMode, ObfuscateDomain};
+use crate::task_03::phone_numbers::{PhoneMode, PhoneNumber, VisibleDigits};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Obfuscation policy that could be stored in a config, e.g. `mask=x;visible=2;domain=true`
+///
+/// The policy is applied by `obfuscate_with_options`, or it could be used as a mode
+/// of `Email::obfuscated_with` and `PhoneNumber::obfuscated_with`, then `mask` is not applied
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ObfuscationOptions {
+    /// A character that replaces the hidden characters
+    pub mask: char,
+    /// A number of trailing digits of a phone number that are left visible
+    pub visible: usize,
+    /// Whether the domain of an email is masked as well
+    pub domain: bool,
+}
+
+impl Default for ObfuscationOptions {
+    fn default() -> Self {
+        ObfuscationOptions {
+            mask: '*',
+            visible: 4,
+            domain: false,
+        }
+    }
+}
+
+/// Parses `;`-separated `key=value` pairs, the missing keys are set to their defaults.
+/// The mask could be quoted, e.g. `mask=';'`, to use a separator or a whitespace as the mask.
+impl FromStr for ObfuscationOptions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = ObfuscationOptions::default();
+
+        for pair in split_pairs(s).filter(|pair| !pair.trim().is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("option `{}` is not a `key=value` pair", pair))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "mask" => {
+                    let mut chars = value.chars();
+                    options.mask = match (chars.next(), chars.next(), chars.next(), chars.next()) {
+                        (Some(c), None, None, None) => c,
+                        (Some('\''), Some(c), Some('\''), None) => c,
+                        _ => return Err(format!("mask must be a single character: `{}`", value)),
+                    };
+                }
+                "visible" => {
+                    options.visible = value
+                        .parse()
+                        .map_err(|_| format!("visible must be a number: `{}`", value))?;
+                }
+                "domain" => {
+                    options.domain = value
+                        .parse()
+                        .map_err(|_| format!("domain must be `true` or `false`: `{}`", value))?;
+                }
+                _ => return Err(format!("unknown option key: `{}`", key)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Splits the `s` on `;`, except the quoted ones, e.g. `mask=';'`
+fn split_pairs(s: &str) -> impl Iterator<Item = &str> {
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    let mut pairs = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i].1 {
+            '\'' if chars.get(i + 2).map(|&(_, ch)| ch) == Some('\'') => i += 3,
+            ';' => {
+                pairs.push(&s[start..chars[i].0]);
+                start = chars[i].0 + 1;
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    pairs.push(&s[start..]);
+
+    pairs.into_iter()
+}
+
+/// The mask is quoted if it wouldn't be parsed back otherwise, e.g. `mask=' '`
+impl Display for ObfuscationOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let needs_quotes = matches!(self.mask, ';' | '=' | '\'') || self.mask.is_whitespace();
+        if needs_quotes {
+            write!(f, "mask='{}'", self.mask)?;
+        } else {
+            write!(f, "mask={}", self.mask)?;
+        }
+        write!(f, ";visible={};domain={}", self.visible, self.domain)
+    }
+}
+
+impl EmailMode for ObfuscationOptions {
+    fn apply(self, email: &mut Email) {
+        if self.domain {
+            ObfuscateDomain.apply(email);
+        }
+    }
+}
+
+impl PhoneMode for ObfuscationOptions {
+    fn apply(self, phone: &mut PhoneNumber) {
+        VisibleDigits(self.visible).apply(phone);
+    }
+}
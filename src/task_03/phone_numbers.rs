@@ -1,74 +1,452 @@
 use crate::task_03::{Obfuscatable, Obfuscated};
+use std::convert::TryFrom;
 use std::fmt;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Display, Formatter, Write};
 use std::str::FromStr;
 
 /// A simplified representation of phone numbers
 pub struct PhoneNumber {
     has_plus_prefix: bool,
-    parts: Vec<u64>,
+    /// Groups of digits, kept as strings to preserve the leading zeros
+    parts: Vec<String>,
+    /// Original separators around the `parts`, there is one more separator than parts:
+    /// the leading one, the ones between the parts, and the trailing one
+    separators: Vec<String>,
+    mask: Mask,
+    /// A number of leading digits that are left visible, 0 unless `PhoneMask` is applied
+    visible_head: usize,
+    keep_country_code: bool,
+    plus_style: PlusStyle,
+    /// The trailing extension, i.e. its delimiter with the surrounding whitespace, e.g. ` x`,
+    /// and its digits
+    extension: Option<(String, String)>,
+    mask_extension: bool,
 }
 
+/// A mode that changes the way a phone number is obfuscated, see `PhoneNumber::obfuscated_with`
+pub trait PhoneMode {
+    fn apply(self, phone: &mut PhoneNumber);
+}
+
+/// Characters that separate groups of digits in a phone number
+pub const GROUP_SEPARATORS: [char; 5] = [' ', '-', '.', '(', ')'];
+
+/// A minimal number of digits of a phone number that is recognized by the `PhoneDetector`,
+/// shorter numeric sequences, e.g. `1 2`, are unlikely to be phone numbers
+pub const MIN_DIGITS: usize = 7;
+
+/// A number of trailing digits that are left visible when a phone number is obfuscated
+#[derive(Copy, Clone, Debug)]
+pub struct VisibleDigits(pub usize);
+
+impl Default for VisibleDigits {
+    fn default() -> Self {
+        VisibleDigits(4)
+    }
+}
+
+impl PhoneMode for VisibleDigits {
+    fn apply(self, phone: &mut PhoneNumber) {
+        phone.mask = Mask::Visible(self.0);
+    }
+}
+
+/// A strategy that decides how many digits of a phone number are masked
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Mask {
+    /// The given number of trailing digits is left visible, the rest is masked
+    Visible(usize),
+    /// The given percentage of digits is masked from the front, rounded to the nearest digit.
+    /// Values above 100 are treated as 100.
+    Percentage(u8),
+}
+
+impl Mask {
+    /// Returns a number of trailing digits that are left visible
+    fn visible_digits(self, total_digits: usize) -> usize {
+        match self {
+            Mask::Visible(visible) => visible,
+            Mask::Percentage(percentage) => {
+                let percentage = percentage.min(100) as usize;
+                let hidden = (total_digits * percentage + 50) / 100;
+                total_digits - hidden
+            }
+        }
+    }
+}
+
+impl PhoneMode for Mask {
+    fn apply(self, phone: &mut PhoneNumber) {
+        phone.mask = self;
+    }
+}
+
+/// A number of digits that are left visible at the start and at the end of a phone number,
+/// only the digits in the middle are masked, e.g. `+44 1** *** *89` for 3 and 2.
+///
+/// If there are fewer digits than `visible_head` and `visible_tail` together, all of them
+/// are visible
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PhoneMask {
+    pub visible_head: usize,
+    pub visible_tail: usize,
+}
+
+impl PhoneMode for PhoneMask {
+    fn apply(self, phone: &mut PhoneNumber) {
+        phone.mask = Mask::Visible(self.visible_tail);
+        phone.visible_head = self.visible_head;
+    }
+}
+
+/// Obfuscation mode that keeps the country code visible, e.g. `+44 *** **6 789`.
+///
+/// The country code is the first group of digits after the plus prefix, hence the numbers
+/// without the prefix are obfuscated as usual.
+#[derive(Copy, Clone, Debug)]
+pub struct KeepCountryCode;
+
+impl PhoneMode for KeepCountryCode {
+    fn apply(self, phone: &mut PhoneNumber) {
+        phone.keep_country_code = true;
+    }
+}
+
+/// The way the international prefix is rendered in the obfuscated number,
+/// it is only rendered for the numbers that had the plus prefix
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum PlusStyle {
+    /// `+44 *** **6 789`, the default
+    #[default]
+    Plus,
+    /// `0044 *** **6 789`
+    DoubleZero,
+    /// `44 *** **6 789`, the prefix is dropped
+    None,
+}
+
+impl PhoneMode for PlusStyle {
+    fn apply(self, phone: &mut PhoneNumber) {
+        phone.plus_style = self;
+    }
+}
+
+/// Obfuscation mode that masks the digits of the extension as well, e.g. `+** *** **6 789 x**`.
+/// The extension is visible otherwise.
+#[derive(Copy, Clone, Debug)]
+pub struct MaskExtension;
+
+impl PhoneMode for MaskExtension {
+    fn apply(self, phone: &mut PhoneNumber) {
+        phone.mask_extension = true;
+    }
+}
+
+/// Delimiters of the extension, the case is ignored, e.g. `x42`, `ext 42` or `Ext. 42`
+const EXTENSION_DELIMITERS: [&str; 3] = ["ext.", "ext", "x"];
+
+/// Splits the trailing extension off the number, returns the number, and the delimiter
+/// with the digits of the extension if there is one
+fn split_extension(s: &str) -> (&str, Option<(&str, &str)>) {
+    let digits_start = s.trim_end_matches(|ch: char| ch.is_ascii_digit()).len();
+    if digits_start == s.len() {
+        return (s, None);
+    }
+
+    let rest = s[..digits_start].trim_end();
+    for delimiter in EXTENSION_DELIMITERS.iter() {
+        let delimiter_start = match rest.len().checked_sub(delimiter.len()) {
+            Some(start) if rest.is_char_boundary(start) => start,
+            _ => continue,
+        };
+        if !rest[delimiter_start..].eq_ignore_ascii_case(delimiter) {
+            continue;
+        }
+
+        let number = rest[..delimiter_start].trim_end();
+        if !number.is_empty() {
+            let extension = (&s[number.len()..digits_start], &s[digits_start..]);
+            return (number, Some(extension));
+        }
+    }
+
+    (s, None)
+}
+
+/// An error returned when a phone number couldn't be parsed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PhoneParseError {
+    /// The input is an empty string
+    EmptyInput,
+    /// The input has nothing but plus signs and separators, e.g. `+`, `+ +` or `( )`
+    NoDigits,
+    /// A group between the separators is not a number
+    InvalidDigitGroup(String),
+}
+
+impl Display for PhoneParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PhoneParseError::EmptyInput => write!(f, "phone number is empty"),
+            PhoneParseError::NoDigits => write!(f, "phone number has no digits"),
+            PhoneParseError::InvalidDigitGroup(group) => {
+                write!(f, "phone number has an invalid group of digits: {}", group)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PhoneParseError {}
+
 /// The same as emails, it is also not easy to parse the numbers. I provide a simple
 /// implementation  that doesn't cover a lot of things. But at least this could be easily
 /// replaced with a better solution, without breaking anything.
 ///
 /// IMHO for a robust parsing of these values
+///
+/// Digit groups could be separated by spaces, dashes, dots or parentheses,
+/// e.g. `+1 (555) 123-4567` or `555.123.4567`. The leading and trailing whitespace is ignored.
+/// A trailing extension, e.g. `+44 123 456 789 x42` or `555.123.4567 ext. 42`, is kept
+/// separately from the number.
 impl FromStr for PhoneNumber {
-    type Err = std::num::ParseIntError;
+    type Err = PhoneParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let str_parts: Vec<&str> = s.trim_start_matches(|sub| sub == '+').split(' ').collect();
+        if s.is_empty() {
+            return Err(PhoneParseError::EmptyInput);
+        }
+
+        // the surrounding whitespace is not a part of the number, e.g. in copy-pasted inputs
+        let s = s.trim();
+        let (s, extension) = split_extension(s);
+
+        // plus signs and separators only, e.g. `+`, ` ` or `+ +`, there is nothing to obfuscate
+        if s.chars()
+            .all(|ch| ch == '+' || GROUP_SEPARATORS.contains(&ch))
+        {
+            return Err(PhoneParseError::NoDigits);
+        }
+
+        let mut str_parts = Vec::new();
+        let mut separators = vec![String::new()];
+        let mut current = String::new();
+
+        for ch in s.trim_start_matches('+').chars() {
+            if GROUP_SEPARATORS.contains(&ch) {
+                if !current.is_empty() {
+                    str_parts.push(std::mem::take(&mut current));
+                    separators.push(String::new());
+                }
+                if let Some(separator) = separators.last_mut() {
+                    separator.push(ch);
+                }
+            } else {
+                current.push(ch);
+            }
+        }
 
-        let mut parts = Vec::with_capacity(str_parts.len());
+        if !current.is_empty() {
+            str_parts.push(current);
+            separators.push(String::new());
+        }
 
-        for part in str_parts {
-            let a_number: u64 = part.parse()?;
-            parts.push(a_number);
+        if let Some(part) = str_parts
+            .iter()
+            .find(|part| !part.chars().all(|c| c.is_ascii_digit()))
+        {
+            return Err(PhoneParseError::InvalidDigitGroup(part.clone()));
         }
 
         Ok(PhoneNumber {
             has_plus_prefix: s.starts_with('+'),
-            parts,
+            parts: str_parts,
+            separators,
+            mask: Mask::Visible(VisibleDigits::default().0),
+            visible_head: 0,
+            keep_country_code: false,
+            plus_style: PlusStyle::default(),
+            extension: extension
+                .map(|(delimiter, digits)| (delimiter.to_string(), digits.to_string())),
+            mask_extension: false,
         })
     }
 }
 
+impl TryFrom<&str> for PhoneNumber {
+    type Error = <PhoneNumber as FromStr>::Err;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl PhoneNumber {
+    /// Returns the number without the plus prefix, with the original separators
+    fn formatted(&self) -> String {
+        let mut output = String::new();
+        for (separator, part) in self.separators.iter().zip(self.parts.iter()) {
+            output.push_str(separator);
+            output.push_str(part);
+        }
+        output.extend(self.separators.last().map(String::as_str));
+        output
+    }
+
+    /// Returns a total number of digits, e.g. 11 for `+44 123 456 789`.
+    /// The digits of the extension are not counted.
+    pub fn digit_count(&self) -> usize {
+        self.parts.iter().map(String::len).sum()
+    }
+
+    /// Returns the digits of the extension, e.g. `42` for `+44 123 456 789 x42`
+    pub fn extension(&self) -> Option<&str> {
+        self.extension.as_ref().map(|(_, digits)| digits.as_str())
+    }
+
+    /// Returns the number in E.164 format, i.e. `+` followed by all the digits, e.g. `+44123456789`
+    ///
+    /// The number is expected to include the country code, it is not added if it's missing
+    pub fn to_e164(&self) -> String {
+        format!("+{}", self.parts.concat())
+    }
+
+    /// Returns the number with all digits but the first `visible` ones replaced by zeros.
+    ///
+    /// Unlike `obfuscated()`, the output is still a number, e.g. `+44 123 000 000`, that can be
+    /// consumed by parsers expecting digits.
+    pub fn obfuscated_zeroed(&self, visible: usize) -> String {
+        let mut seen = 0;
+        let number = self
+            .formatted()
+            .chars()
+            .map(|ch| {
+                if !ch.is_ascii_digit() {
+                    return ch;
+                }
+                seen += 1;
+                if seen <= visible {
+                    ch
+                } else {
+                    '0'
+                }
+            })
+            .collect::<String>();
+
+        if self.has_plus_prefix {
+            format!("+{}", number)
+        } else {
+            number
+        }
+    }
+}
+
 impl Obfuscatable for PhoneNumber {}
 
-impl Display for Obfuscated<PhoneNumber> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        // I just write the visible digits first on the reversed string.
-        // Then, reverse it back.
-        let s = self
-            .0
-            .parts
-            .iter()
-            .map(|n| n.to_string())
-            .collect::<Vec<String>>()
-            .join("-");
-
-        let number_of_visible = 4;
-        let mut visible = 0;
-        let mut output = String::with_capacity(s.len());
-
-        for ch in s.chars().rev() {
-            if ch.is_digit(10) {
-                if visible < number_of_visible {
-                    output.push(ch);
-                    visible += 1;
+impl PhoneNumber {
+    /// The same as `obfuscated()`, but with the given obfuscation mode applied,
+    /// e.g. `VisibleDigits(2)` to leave 2 trailing digits visible instead of the default 4
+    pub fn obfuscated_with<M: PhoneMode>(mut self, mode: M) -> Obfuscated<Self> {
+        mode.apply(&mut self);
+        self.obfuscated()
+    }
+}
+
+impl Obfuscated<PhoneNumber> {
+    /// Writes the obfuscated number into `w`, without allocating an intermediate `String`
+    ///
+    /// The trailing digits are visible, the number of them is known upfront, so the digits
+    /// could be written in a single pass from the front, keeping the original separators
+    pub fn write_to<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let (total_digits, visible_head, visible_tail) = self.visible_digits();
+
+        if self.0.has_plus_prefix {
+            match self.0.plus_style {
+                PlusStyle::Plus => w.write_char('+')?,
+                PlusStyle::DoubleZero => w.write_str("00")?,
+                PlusStyle::None => {}
+            }
+        }
+
+        let mut digits_left = total_digits;
+        for (separator, part) in self.0.separators.iter().zip(self.0.parts.iter()) {
+            w.write_str(separator)?;
+            for ch in part.chars() {
+                let is_head = total_digits - digits_left < visible_head;
+                digits_left -= 1;
+                if is_head || digits_left < visible_tail {
+                    w.write_char(ch)?;
                 } else {
-                    output.push('*');
+                    w.write_char('*')?;
+                }
+            }
+        }
+        if let Some(separator) = self.0.separators.last() {
+            w.write_str(separator)?;
+        }
+
+        if let Some((delimiter, digits)) = &self.0.extension {
+            w.write_str(delimiter)?;
+            if self.0.mask_extension {
+                for _ in digits.chars() {
+                    w.write_char('*')?;
                 }
             } else {
-                output.push('-');
+                w.write_str(digits)?;
             }
         }
 
-        if self.0.has_plus_prefix {
-            write!(f, "+")?;
+        Ok(())
+    }
+
+    /// Returns a number of digits that are masked in the obfuscated number
+    pub fn masked_digit_count(&self) -> usize {
+        let (total_digits, _, _) = self.visible_digits();
+        total_digits - self.visible_digit_count()
+    }
+
+    /// Returns a number of digits that are left visible in the obfuscated number
+    pub fn visible_digit_count(&self) -> usize {
+        let (total_digits, visible_head, visible_tail) = self.visible_digits();
+        (visible_head + visible_tail).min(total_digits)
+    }
+
+    /// Returns a total number of digits, and the numbers of leading and trailing digits
+    /// that are left visible. The visible digits at both ends may overlap.
+    fn visible_digits(&self) -> (usize, usize, usize) {
+        let total_digits = self.0.digit_count();
+        let visible_tail = self.0.mask.visible_digits(total_digits);
+
+        // the country code digits are the first ones
+        let country_code_len = match self.0.parts.first() {
+            Some(country_code) if self.0.keep_country_code && self.0.has_plus_prefix => {
+                country_code.len()
+            }
+            _ => 0,
+        };
+        let visible_head = country_code_len.max(self.0.visible_head);
+
+        (total_digits, visible_head, visible_tail)
+    }
+
+    /// Checks if the `original` phone number would be obfuscated to the same output,
+    /// with the same number of visible digits
+    pub fn matches_plaintext(&self, original: &str) -> bool {
+        match original.parse::<PhoneNumber>() {
+            Ok(mut phone) => {
+                phone.mask = self.0.mask;
+                phone.visible_head = self.0.visible_head;
+                phone.keep_country_code = self.0.keep_country_code;
+                phone.plus_style = self.0.plus_style;
+                phone.mask_extension = self.0.mask_extension;
+                phone.obfuscated().to_string() == self.to_string()
+            }
+            Err(_) => false,
         }
+    }
+}
 
-        write!(f, "{}", output.chars().rev().collect::<String>())
+impl Display for Obfuscated<PhoneNumber> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
     }
 }
@@ -0,0 +1,57 @@
+use crate::task_03::{Obfuscatable, Obfuscated};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// US Social Security Number, e.g. `123-45-6789`
+pub struct Ssn {
+    area: String,
+    group: String,
+    serial: String,
+}
+
+/// Only the `###-##-####` format is accepted, the numbers themselves are not validated
+impl FromStr for Ssn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('-').collect();
+
+        let is_valid = parts.len() == 3
+            && parts
+                .iter()
+                .zip([3, 2, 4].iter())
+                .all(|(part, &len)| part.len() == len && part.chars().all(|c| c.is_ascii_digit()));
+
+        if !is_valid {
+            return Err("not an SSN".into());
+        }
+
+        Ok(Ssn {
+            area: parts[0].into(),
+            group: parts[1].into(),
+            serial: parts[2].into(),
+        })
+    }
+}
+
+impl Obfuscatable for Ssn {}
+
+impl Obfuscated<Ssn> {
+    /// Returns a number of digits that are masked, i.e. the area and the group
+    pub fn masked_char_count(&self) -> usize {
+        self.0.area.len() + self.0.group.len()
+    }
+}
+
+impl Display for Obfuscated<Ssn> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}-{}",
+            "*".repeat(self.0.area.len()),
+            "*".repeat(self.0.group.len()),
+            self.0.serial
+        )
+    }
+}
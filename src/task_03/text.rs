@@ -0,0 +1,148 @@
+use crate::task_03::emails::Email;
+use crate::task_03::phone_numbers::{PhoneNumber, GROUP_SEPARATORS, MIN_DIGITS};
+use crate::task_03::Obfuscatable;
+use chrono::NaiveDate;
+
+/// Date formats that are not treated as phone numbers in a text, even though they have
+/// enough digits, e.g. "01-05-2021"
+const DATE_FORMATS: [&str; 4] = ["%d-%m-%Y", "%m-%d-%Y", "%Y-%m-%d", "%d.%m.%Y"];
+
+/// Punctuation that could follow a value in a sentence, e.g. "call +44 123 456 789."
+const TRAILING_PUNCTUATION: [char; 6] = ['.', ',', ';', ':', '!', '?'];
+
+/// Splits the text into words, each word is followed by the whitespace after it
+fn split_words(input: &str) -> Vec<(&str, &str)> {
+    let mut words = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (word, after) = rest.split_at(word_end);
+        let ws_end = after
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(after.len());
+        let (ws, after) = after.split_at(ws_end);
+        words.push((word, ws));
+        rest = after;
+    }
+
+    words
+}
+
+/// A word that could be a group of digits of a phone number
+fn is_phone_group(word: &str, first: bool) -> bool {
+    let word = if first {
+        word.strip_prefix('+').unwrap_or(word)
+    } else {
+        word
+    };
+    let word = word.trim_end_matches(TRAILING_PUNCTUATION);
+
+    word.chars().any(|c| c.is_ascii_digit())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_digit() || GROUP_SEPARATORS.contains(&c))
+}
+
+/// Checks if the `number` is a date in one of the `DATE_FORMATS`
+fn is_date(number: &str) -> bool {
+    DATE_FORMATS
+        .iter()
+        .any(|format| NaiveDate::parse_from_str(number, format).is_ok())
+}
+
+/// Tries to find a phone number that starts with `words[0]`.
+///
+/// Returns the obfuscated phone number, followed by the trailing punctuation and whitespace,
+/// and the number of words it takes
+fn obfuscate_phone_at(words: &[(&str, &str)]) -> Option<(String, usize)> {
+    let mut len = 0;
+    while len < words.len() && is_phone_group(words[len].0, len == 0) {
+        len += 1;
+        // a phone number doesn't span several lines
+        if words[len - 1].1.chars().any(|c| c != ' ') {
+            break;
+        }
+    }
+
+    // shorten the candidate until it's a valid phone number
+    while len > 0 {
+        let mut candidate = String::new();
+        for (i, (word, ws)) in words[..len].iter().enumerate() {
+            candidate.push_str(word);
+            if i + 1 < len {
+                candidate.push_str(ws);
+            }
+        }
+
+        let number = candidate.trim_end_matches(TRAILING_PUNCTUATION);
+        let digits = number.chars().filter(|c| c.is_ascii_digit()).count();
+        // the same threshold as the one of the `PhoneDetector`, so `obfuscate` agrees
+        if digits < MIN_DIGITS || is_date(number) {
+            return None;
+        }
+
+        if let Ok(phone) = number.parse::<PhoneNumber>() {
+            let output = format!(
+                "{}{}{}",
+                phone.obfuscated(),
+                &candidate[number.len()..],
+                words[len - 1].1
+            );
+            return Some((output, len));
+        }
+
+        len -= 1;
+    }
+
+    None
+}
+
+/// Obfuscates the word if it's an email surrounded with punctuation, e.g. `<a@b.com>,`
+fn obfuscate_email_word(word: &str) -> Option<String> {
+    let start = word.find(char::is_alphanumeric)?;
+    let end = word.rfind(char::is_alphanumeric)?;
+    let end = end + word[end..].chars().next()?.len_utf8();
+
+    let email = word[start..end].parse::<Email>().ok()?;
+    Some(format!(
+        "{}{}{}",
+        &word[..start],
+        email.obfuscated(),
+        &word[end..]
+    ))
+}
+
+/// Obfuscates all emails and phone numbers found in the text, the rest of the text is kept
+///
+/// Usage example:
+///
+/// ```rust
+/// let obfuscated = obfuscate_text("Contact a@b.com or +44 123 456 789 today");
+/// println!("{}", obfuscated); // prints "Contact a@b.com or +** *** **6 789 today"
+/// ```
+pub fn obfuscate_text(input: &str) -> String {
+    let leading_ws = input.len() - input.trim_start().len();
+    let mut output = String::from(&input[..leading_ws]);
+
+    let words = split_words(&input[leading_ws..]);
+    let mut i = 0;
+
+    while i < words.len() {
+        if let Some((phone, len)) = obfuscate_phone_at(&words[i..]) {
+            output.push_str(&phone);
+            i += len;
+            continue;
+        }
+
+        let (word, ws) = words[i];
+        match obfuscate_email_word(word) {
+            Some(email) => output.push_str(&email),
+            None => output.push_str(word),
+        }
+        output.push_str(ws);
+        i += 1;
+    }
+
+    output
+}
@@ -0,0 +1,52 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn run(args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_obfuscate"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn obfuscates_lines() {
+    assert_eq!(
+        "l********t@domain-name.com\nnot a secret\n+** *** **6 789\n",
+        run(
+            &[],
+            "local-part@domain-name.com\nnot a secret\n+44 123 456 789\n"
+        )
+    );
+}
+
+#[test]
+fn obfuscates_text() {
+    assert_eq!(
+        "mail l********t@domain-name.com\n",
+        run(&["--text"], "mail local-part@domain-name.com\n")
+    );
+}
+
+#[test]
+fn rejects_unknown_flags() {
+    let status = Command::new(env!("CARGO_BIN_EXE_obfuscate"))
+        .arg("--unknown")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .unwrap();
+    assert_eq!(Some(2), status.code());
+}